@@ -0,0 +1,48 @@
+//! Measure how long it takes to print type names, which happens for every value when printing
+//! CLIF text; the benchmarking is feature-gated on `type-display-bench`.
+
+#[cfg(feature = "type-display-bench")]
+mod type_display {
+    use core::fmt::Write;
+    use cranelift_codegen::ir::types::{self, Type};
+    use criterion::{criterion_group, Criterion};
+
+    fn type_display_benchmarks(c: &mut Criterion) {
+        let types: Vec<Type> = Type::all_vector_types(512).collect();
+        let mut buf = String::new();
+
+        c.bench_function("Display for Type (lanes and vectors)", |b| {
+            b.iter(|| {
+                buf.clear();
+                for ty in &types {
+                    write!(buf, "{ty}").unwrap();
+                }
+            });
+        });
+
+        c.bench_function("Display for Type (i32)", |b| {
+            b.iter(|| {
+                buf.clear();
+                write!(buf, "{}", criterion::black_box(types::I32)).unwrap();
+            });
+        });
+    }
+    criterion_group!(benches, type_display_benchmarks);
+
+    /// Using an inner module to feature-gate the benchmarks means that we must
+    /// manually specify how to run the benchmarks (see `criterion_main!`).
+    pub fn run_benchmarks() {
+        benches();
+        Criterion::default().configure_from_args().final_summary();
+    }
+}
+
+fn main() {
+    #[cfg(feature = "type-display-bench")]
+    type_display::run_benchmarks();
+
+    #[cfg(not(feature = "type-display-bench"))]
+    println!(
+        "Unable to run the type-display benchmark; the `type-display-bench` feature must be enabled in Cargo.",
+    );
+}
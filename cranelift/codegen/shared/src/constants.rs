@@ -14,6 +14,9 @@
 // in the next highest 4 bits, giving a range of 2-256 lanes.
 
 // Dynamic vector types are encoded similarly.
+//
+// `cranelift_codegen::ir::types` checks at compile time that the lane types fit in the low 4
+// bits and that the widest fixed and dynamic vectors stay within their ranges.
 
 /// Start of the lane types.
 pub const LANE_BASE: u16 = 0x70;
@@ -1,6 +1,11 @@
 //! Common types for the Cranelift code generator.
 
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug, Display, Formatter};
+use core::str::FromStr;
 use cranelift_codegen_shared::constants;
 #[cfg(feature = "enable-serde")]
 use serde_derive::{Deserialize, Serialize};
@@ -21,6 +26,9 @@ use target_lexicon::{PointerWidth, Triple};
 /// Note that this is encoded in a `u16` currently for extensibility,
 /// but allows only 14 bits to be used due to some bitpacking tricks
 /// in the CLIF data structures.
+///
+/// The `Hash` implementation hashes the bit-level representation returned by [`Type::repr`].
+/// It is stable within a release of this crate, but the encoding may change between releases.
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "enable-serde", derive(Serialize, Deserialize))]
 pub struct Type(u16);
@@ -33,11 +41,75 @@ pub const INVALID: Type = Type(0);
 // 512-bit SIMD vectors.
 include!(concat!(env!("OUT_DIR"), "/types.rs"));
 
+// Check the invariants of the encoding described in `cranelift_codegen_shared::constants`: every
+// lane type code fits in the low 4 bits above `LANE_BASE`, and shifting in a `log2_lane_count` of
+// up to 8 (256 lanes) can't run into the next range.
+const _: () = assert!(constants::LANE_BASE & 0x0f == 0);
+const _: () = assert!(constants::LANE_BASE + 0x10 == constants::VECTOR_BASE);
+const _: () = assert!(I8.0 >= constants::LANE_BASE && F128.0 < constants::REFERENCE_BASE);
+const _: () = assert!(F128.0 + (8 << 4) < constants::DYNAMIC_VECTOR_BASE);
+const _: () = assert!(F128.0 + (8 << 4) + constants::VECTOR_BASE < 0x180);
+
+/// Get the [`Type`](crate::ir::types::Type) with the given name, such as `typ!(i32)` or
+/// `typ!(f32x4)`.
+///
+/// The name is parsed at compile time, so an invalid name is a compile error.
+#[macro_export]
+macro_rules! typ {
+    ($name:ident) => {{
+        const TY: $crate::ir::types::Type =
+            match $crate::ir::types::Type::from_name(stringify!($name)) {
+                Ok(ty) => ty,
+                Err(_) => panic!(concat!("invalid type name `", stringify!($name), "`")),
+            };
+        TY
+    }};
+}
+
+/// The commonly used types, for glob imports.
+///
+/// This contains [`Type`], `INVALID`, the lane types and the 128-bit SIMD vector types.
+pub mod prelude {
+    pub use super::{
+        Type, F128, F16, F16X8, F32, F32X4, F64, F64X2, I128, I16, I16X8, I32, I32X4, I64, I64X2,
+        I8, I8X16, INVALID,
+    };
+}
+
 impl Type {
+    /// Iterate over all of the lane types, which are the scalar types that can also appear as
+    /// the lane type of a SIMD vector.
+    ///
+    /// Integer types are yielded first, followed by floating point types, each from narrowest to
+    /// widest.
+    pub fn all_lane_types() -> impl Iterator<Item = Type> {
+        Type::int_types().chain(Type::float_types())
+    }
+
+    /// Iterate over the scalar integer types, from narrowest to widest.
+    pub fn int_types() -> impl Iterator<Item = Type> {
+        [I8, I16, I32, I64, I128].into_iter()
+    }
+
+    /// Iterate over the scalar floating point types, from narrowest to widest.
+    pub fn float_types() -> impl Iterator<Item = Type> {
+        [F16, F32, F64, F128].into_iter()
+    }
+
+    /// Iterate over all lane types and fixed-size SIMD vector types with at most `max_bits`
+    /// bits, such as the types supported by a target with `max_bits`-wide vector registers.
+    ///
+    /// Types are grouped by lane type, in the order of [`Self::all_lane_types`], and yielded
+    /// from fewest to most lanes.
+    pub fn all_vector_types(max_bits: u32) -> impl Iterator<Item = Type> {
+        Self::all_lane_types()
+            .flat_map(move |lane| lane.all_lanes().take_while(move |ty| ty.bits() <= max_bits))
+    }
+
     /// Get the lane type of this SIMD vector type.
     ///
     /// A lane type is the same as a SIMD vector type with one lane, so it returns itself.
-    pub fn lane_type(self) -> Self {
+    pub const fn lane_type(self) -> Self {
         if self.0 < constants::VECTOR_BASE {
             self
         } else {
@@ -48,12 +120,53 @@ impl Type {
     /// The type transformation that returns the lane type of a type variable; it is just a
     /// renaming of lane_type() to be used in context where we think in terms of type variable
     /// transformations.
-    pub fn lane_of(self) -> Self {
+    pub const fn lane_of(self) -> Self {
+        self.lane_type()
+    }
+
+    /// Get the result type of a horizontal reduction over the lanes of this type, such as
+    /// `I32` for summing the lanes of an `I32X4`.
+    ///
+    /// This is the same as [`Self::lane_type`].
+    pub const fn reduce_to_scalar(self) -> Self {
         self.lane_type()
     }
 
+    /// Get the scalar type to accumulate a horizontal sum of the integer lanes of this type in.
+    ///
+    /// The accumulator is twice as wide as a lane, such as `I16` for `I8X16`, which is enough
+    /// to sum up to 256 lanes without overflow. Returns `None` for `I128` lanes, which have no
+    /// wider type, and for types without integer lanes, whose reductions follow other rules.
+    pub fn reduction_accumulator(self) -> Option<Self> {
+        self.lane_type().wider_int()
+    }
+
+    /// Get the type of the lane at `index`, or `None` if `index` is out of bounds.
+    ///
+    /// Scalar types have a single lane at index 0. Dynamic vectors have no statically known
+    /// lanes, so this always returns `None` for them.
+    pub fn lane_type_at(self, index: u32) -> Option<Self> {
+        if index < self.lane_count() {
+            Some(self.lane_type())
+        } else {
+            None
+        }
+    }
+
+    /// Get the byte offset of the lane at `index` in memory, or `None` if `index` is out of
+    /// bounds.
+    ///
+    /// Like [`Self::lane_type_at`], this always returns `None` for dynamic vectors. It also
+    /// returns `None` for `INVALID`, which has no layout.
+    pub fn lane_byte_offset(self, index: u32) -> Option<u32> {
+        match self.lane_type_at(index) {
+            Some(lane) if lane.bytes() != 0 => Some(index * lane.bytes()),
+            _ => None,
+        }
+    }
+
     /// Get log_2 of the number of bits in a lane.
-    pub fn log2_lane_bits(self) -> u32 {
+    pub const fn log2_lane_bits(self) -> u32 {
         match self.lane_type() {
             I8 => 3,
             I16 | F16 => 4,
@@ -65,7 +178,7 @@ impl Type {
     }
 
     /// Get the number of bits in a lane.
-    pub fn lane_bits(self) -> u32 {
+    pub const fn lane_bits(self) -> u32 {
         match self.lane_type() {
             I8 => 8,
             I16 | F16 => 16,
@@ -100,10 +213,64 @@ impl Type {
         }
     }
 
+    /// Get the maximum unsigned value of each lane in the type, or `None` if the lanes aren't
+    /// integers.
+    ///
+    /// This is the maximum of [`Self::bounds`], as an integer rather than a bit pattern.
+    pub fn unsigned_max(self) -> Option<u128> {
+        if self.lane_type().is_int() {
+            Some(self.bounds(false).1)
+        } else {
+            None
+        }
+    }
+
+    /// Get the maximum signed value of each lane in the type, or `None` if the lanes aren't
+    /// integers.
+    pub fn signed_max(self) -> Option<i128> {
+        if self.lane_type().is_int() {
+            Some(self.bounds(true).1 as i128)
+        } else {
+            None
+        }
+    }
+
+    /// Get the minimum signed value of each lane in the type, or `None` if the lanes aren't
+    /// integers.
+    pub fn signed_min(self) -> Option<i128> {
+        if self.lane_type().is_int() {
+            Some(self.bounds(true).0 as i128)
+        } else {
+            None
+        }
+    }
+
+    /// Can `value` be used as the immediate of an `iconst` of this type, interpreted as a
+    /// signed integer?
+    ///
+    /// Returns `false` for anything but scalar integer types.
+    pub fn iconst_fits_signed(self, value: i64) -> bool {
+        let value = i128::from(value);
+        self.is_int()
+            && self.signed_min().is_some_and(|min| min <= value)
+            && self.signed_max().is_some_and(|max| value <= max)
+    }
+
+    /// Can `value` be used as the immediate of an `iconst` of this type, interpreted as an
+    /// unsigned integer?
+    ///
+    /// Returns `false` for anything but scalar integer types.
+    pub fn iconst_fits_unsigned(self, value: u64) -> bool {
+        self.is_int()
+            && self
+                .unsigned_max()
+                .is_some_and(|max| u128::from(value) <= max)
+    }
+
     /// Get an integer type with the requested number of bits.
     ///
     /// For the same thing but in *bytes*, use [`Self::int_with_byte_size`].
-    pub fn int(bits: u16) -> Option<Self> {
+    pub const fn int(bits: u16) -> Option<Self> {
         match bits {
             8 => Some(I8),
             16 => Some(I16),
@@ -121,10 +288,54 @@ impl Type {
         Self::int(bytes.checked_mul(8)?)
     }
 
+    /// Get a floating point type with the requested number of bits.
+    pub const fn float(bits: u16) -> Option<Self> {
+        match bits {
+            16 => Some(F16),
+            32 => Some(F32),
+            64 => Some(F64),
+            128 => Some(F128),
+            _ => None,
+        }
+    }
+
+    /// Get the narrowest scalar integer type, `I8`.
+    pub const fn smallest_int() -> Self {
+        I8
+    }
+
+    /// Get the widest scalar integer type, `I128`.
+    pub const fn largest_int() -> Self {
+        I128
+    }
+
+    /// Get the narrowest scalar floating point type, `F16`.
+    pub const fn smallest_float() -> Self {
+        F16
+    }
+
+    /// Get the widest scalar floating point type, `F128`.
+    pub const fn largest_float() -> Self {
+        F128
+    }
+
     /// Get a type with the same number of lanes as `self`, but using `lane` as the lane type.
     fn replace_lanes(self, lane: Self) -> Self {
         debug_assert!(lane.is_lane() && !self.is_special());
-        Self((lane.0 & 0x0f) | (self.0 & 0xf0))
+        Self((lane.0 & 0x0f) | (self.0 & !0x0f))
+    }
+
+    /// Get a type with the same number of lanes as this type, but with the lanes replaced by
+    /// `lane`.
+    ///
+    /// Lane types are treated as vectors with one lane, so the result is `lane` itself. Returns
+    /// `None` if `lane` isn't a lane type or this type is `INVALID`.
+    pub fn with_lane_type(self, lane: Self) -> Option<Self> {
+        if lane.is_lane() && !self.is_special() {
+            Some(self.replace_lanes(lane))
+        } else {
+            None
+        }
     }
 
     /// Get a type with the same number of lanes as this type, but with the lanes replaced by
@@ -155,6 +366,21 @@ impl Type {
         }
     }
 
+    /// Get the result type of an `icmp` or `fcmp` with operands of this type.
+    ///
+    /// This is [`Self::as_truthy`], except that dynamic vectors also produce a vector of the same
+    /// shape. Returns `None` for types without integer or float lanes.
+    pub fn compare_result(self) -> Option<Self> {
+        let lane = self.lane_type();
+        if !lane.is_int() && !lane.is_float() {
+            None
+        } else if self.is_lane() {
+            Some(self.as_truthy())
+        } else {
+            Some(self.as_truthy_pedantic())
+        }
+    }
+
     /// Get a type with the same number of lanes as this type, but with the lanes replaced by
     /// integers of the same size.
     pub fn as_int(self) -> Self {
@@ -168,6 +394,56 @@ impl Type {
         })
     }
 
+    /// Get the type of a shuffle index vector with one byte per lane of this vector type, such
+    /// as `I8X4` for `I32X4`.
+    ///
+    /// Returns `None` for anything but fixed vector types.
+    pub fn shuffle_mask_type(self) -> Option<Self> {
+        if self.is_vector() {
+            I8.by(self.lane_count())
+        } else {
+            None
+        }
+    }
+
+    /// Get the scalar integer type with the same size as this scalar type, such as `I32` for
+    /// `F32`, for bitcasting scalars.
+    ///
+    /// Integer types are returned unchanged. Returns `None` for vectors and `INVALID`; see
+    /// [`Self::bit_mask_type`] for vectors.
+    pub fn bitcast_to_int(self) -> Option<Self> {
+        if self.is_lane() {
+            self.bit_mask_type()
+        } else {
+            None
+        }
+    }
+
+    /// Get the largest shift amount that is meaningful for shifting the integer lanes of this
+    /// type, which is one less than the lane width.
+    ///
+    /// Returns `None` for types without integer lanes.
+    pub const fn max_shift_amount(self) -> Option<u8> {
+        if self.lane_type().is_int() {
+            Some((self.lane_bits() - 1) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Get the type of the mask used to select bits of this type, as used by `bitselect`.
+    ///
+    /// This is the same as [`Self::as_int`], such as `I32X4` for `F32X4`, but returns `None`
+    /// instead of panicking when the lanes aren't integers or floats.
+    pub fn bit_mask_type(self) -> Option<Self> {
+        let lane = self.lane_type();
+        if lane.is_int() || lane.is_float() {
+            Some(self.as_int())
+        } else {
+            None
+        }
+    }
+
     /// Get a type with the same number of lanes as this type, but with lanes that are half the
     /// number of bits.
     pub fn half_width(self) -> Option<Self> {
@@ -198,37 +474,111 @@ impl Type {
         }))
     }
 
+    /// Get a type with the same number of lanes as this type, but with integer lanes that are
+    /// twice the number of bits.
+    ///
+    /// Unlike [`Self::double_width`], this returns `None` for anything but integer lanes.
+    pub fn wider_int(self) -> Option<Self> {
+        if self.lane_type().is_int() {
+            self.double_width()
+        } else {
+            None
+        }
+    }
+
+    /// Get a type with the same number of lanes as this type, but with integer lanes that are
+    /// half the number of bits.
+    ///
+    /// Unlike [`Self::half_width`], this returns `None` for anything but integer lanes.
+    pub fn narrower_int(self) -> Option<Self> {
+        if self.lane_type().is_int() {
+            self.half_width()
+        } else {
+            None
+        }
+    }
+
+    /// Get a type with the same number of lanes as this type, but with floating point lanes
+    /// that are twice the number of bits, following the chain `F16`, `F32`, `F64`, `F128`.
+    ///
+    /// Unlike [`Self::double_width`], this returns `None` for anything but float lanes.
+    pub fn wider_float(self) -> Option<Self> {
+        if self.lane_type().is_float() {
+            self.double_width()
+        } else {
+            None
+        }
+    }
+
+    /// Get a type with the same number of lanes as this type, but with floating point lanes
+    /// that are half the number of bits.
+    ///
+    /// Unlike [`Self::half_width`], this returns `None` for anything but float lanes.
+    pub fn narrower_float(self) -> Option<Self> {
+        if self.lane_type().is_float() {
+            self.half_width()
+        } else {
+            None
+        }
+    }
+
     /// Is this the INVALID type?
-    pub fn is_invalid(self) -> bool {
-        self == INVALID
+    pub const fn is_invalid(self) -> bool {
+        self.0 == INVALID.0
     }
 
     /// Is this a special type?
-    pub fn is_special(self) -> bool {
+    pub const fn is_special(self) -> bool {
         self.0 < constants::LANE_BASE
     }
 
     /// Is this a lane type?
     ///
     /// This is a scalar type that can also appear as the lane type of a SIMD vector.
-    pub fn is_lane(self) -> bool {
+    pub const fn is_lane(self) -> bool {
         constants::LANE_BASE <= self.0 && self.0 < constants::VECTOR_BASE
     }
 
+    /// Can this type be used as the lane type of a SIMD vector?
+    ///
+    /// This is true for every lane type and false for `INVALID` and vector types. For these
+    /// types [`Self::checked_by`] won't fail with [`TypeError::InvalidLane`].
+    pub const fn is_simd_lane_legal(self) -> bool {
+        self.is_lane()
+    }
+
+    /// Is this the lane type of `vector`?
+    ///
+    /// This is true when `self` is a lane type and `vector` is a vector type, or lane type,
+    /// made of `self` lanes. For example `I32.is_lane_of(I32X4)` is true.
+    pub fn is_lane_of(self, vector: Self) -> bool {
+        self.is_lane() && self == vector.lane_type()
+    }
+
     /// Is this a SIMD vector type?
     ///
     /// A vector type has 2 or more lanes.
-    pub fn is_vector(self) -> bool {
+    pub const fn is_vector(self) -> bool {
         self.0 >= constants::VECTOR_BASE && !self.is_dynamic_vector()
     }
 
+    /// Is this a fixed SIMD vector type with integer lanes?
+    pub const fn is_int_vector(self) -> bool {
+        self.is_vector() && self.lane_type().is_int()
+    }
+
+    /// Is this a fixed SIMD vector type with floating point lanes?
+    pub const fn is_float_vector(self) -> bool {
+        self.is_vector() && self.lane_type().is_float()
+    }
+
     /// Is this a SIMD vector type with a runtime number of lanes?
-    pub fn is_dynamic_vector(self) -> bool {
+    pub const fn is_dynamic_vector(self) -> bool {
         self.0 >= constants::DYNAMIC_VECTOR_BASE
     }
 
     /// Is this a scalar integer type?
-    pub fn is_int(self) -> bool {
+    pub const fn is_int(self) -> bool {
         match self {
             I8 | I16 | I32 | I64 | I128 => true,
             _ => false,
@@ -236,7 +586,7 @@ impl Type {
     }
 
     /// Is this a scalar floating point type?
-    pub fn is_float(self) -> bool {
+    pub const fn is_float(self) -> bool {
         match self {
             F16 | F32 | F64 | F128 => true,
             _ => false,
@@ -249,7 +599,7 @@ impl Type {
     /// will be a number in the range 0-8.
     ///
     /// A scalar type is the same as a SIMD vector type with one lane, so it returns 0.
-    pub fn log2_lane_count(self) -> u32 {
+    pub const fn log2_lane_count(self) -> u32 {
         if self.is_dynamic_vector() {
             0
         } else {
@@ -258,7 +608,7 @@ impl Type {
     }
 
     /// Get log_2 of the number of lanes in this vector/dynamic type.
-    pub fn log2_min_lane_count(self) -> u32 {
+    pub const fn log2_min_lane_count(self) -> u32 {
         if self.is_dynamic_vector() {
             (self
                 .0
@@ -272,7 +622,7 @@ impl Type {
     /// Get the number of lanes in this SIMD vector type.
     ///
     /// A scalar type is the same as a SIMD vector type with one lane, so it returns 1.
-    pub fn lane_count(self) -> u32 {
+    pub const fn lane_count(self) -> u32 {
         if self.is_dynamic_vector() {
             0
         } else {
@@ -281,7 +631,9 @@ impl Type {
     }
 
     /// Get the total number of bits used to represent this type.
-    pub fn bits(self) -> u32 {
+    ///
+    /// This is a `u32` so that the widest vectors, 256 lanes of 128 bits, can't overflow.
+    pub const fn bits(self) -> u32 {
         if self.is_dynamic_vector() {
             0
         } else {
@@ -289,9 +641,32 @@ impl Type {
         }
     }
 
+    /// Get [`Self::bits`] rounded up to a power of two.
+    ///
+    /// This is 0 for types whose `bits` is 0, such as `INVALID`.
+    pub const fn bits_rounded_up_pow2(self) -> u32 {
+        match self.bits() {
+            0 => 0,
+            bits => bits.next_power_of_two(),
+        }
+    }
+
+    /// Get the total number of bits used to represent this type, or `None` if this isn't
+    /// `INVALID` or a valid type.
+    ///
+    /// [`Self::bits`] returns 0 for malformed types, which makes them hard to tell apart from
+    /// `INVALID` and dynamic vectors.
+    pub fn checked_bits(self) -> Option<u32> {
+        if self.is_invalid() || self.is_canonical() {
+            Some(self.bits())
+        } else {
+            None
+        }
+    }
+
     /// Get the minimum of lanes in this SIMD vector type, this supports both fixed and
     /// dynamic types.
-    pub fn min_lane_count(self) -> u32 {
+    pub const fn min_lane_count(self) -> u32 {
         if self.is_dynamic_vector() {
             1 << self.log2_min_lane_count()
         } else {
@@ -300,7 +675,7 @@ impl Type {
     }
 
     /// Get the minimum number of bits used to represent this type.
-    pub fn min_bits(self) -> u32 {
+    pub const fn min_bits(self) -> u32 {
         if self.is_dynamic_vector() {
             self.lane_bits() * self.min_lane_count()
         } else {
@@ -308,53 +683,312 @@ impl Type {
         }
     }
 
+    /// Does this type have an all-zeros value, as produced by `iconst` or `vconst` of zero?
+    ///
+    /// This is true for every type except `INVALID` and malformed types.
+    pub const fn has_zero(self) -> bool {
+        self.lane_bits() != 0
+    }
+
+    /// Get the number of bits in the all-zeros value of this type.
+    ///
+    /// This is [`Self::bits`] for fixed-size types and the minimum size for dynamic vectors.
+    /// It is 0 for types without a zero value.
+    pub const fn zero_bits(self) -> u32 {
+        self.min_bits()
+    }
+
     /// Get the number of bytes used to store this type in memory.
-    pub fn bytes(self) -> u32 {
+    pub const fn bytes(self) -> u32 {
         (self.bits() + 7) / 8
     }
 
+    /// Does a value of this type fit in a register of `reg_bits` bits?
+    ///
+    /// This is false for types without a fixed size, such as `INVALID` and dynamic vectors.
+    pub const fn fits_in_register(self, reg_bits: u32) -> bool {
+        self.bits() != 0 && self.bits() <= reg_bits
+    }
+
+    /// Get the number of `reg_bits`-bit registers needed to hold a value of this type, such as
+    /// 4 for an `I32X16` in 128-bit registers or 2 for an `I64` in 32-bit registers.
+    ///
+    /// This is 0 for types without a fixed size, such as `INVALID` and dynamic vectors. Returns
+    /// `None` if `reg_bits` is 0.
+    pub const fn register_count(self, reg_bits: u32) -> Option<u32> {
+        if reg_bits == 0 {
+            None
+        } else {
+            Some(self.bits().div_ceil(reg_bits))
+        }
+    }
+
+    /// Get the type of each part when splitting this vector type across
+    /// [`Self::register_count`] registers of `reg_bits` bits, such as `I32X4` for an `I32X16`
+    /// in 128-bit registers.
+    ///
+    /// Returns `None` if this isn't a fixed vector type, if it already fits in a register, or if
+    /// a register doesn't hold a whole number of lanes.
+    pub fn register_subtype(self, reg_bits: u32) -> Option<Self> {
+        if !self.is_vector() || self.bits() <= reg_bits || reg_bits % self.lane_bits() != 0 {
+            return None;
+        }
+        self.lane_type().by(reg_bits / self.lane_bits())
+    }
+
+    /// Get the type a value of this integer type is extended to when it is passed in a
+    /// register of `reg_bits` bits.
+    ///
+    /// Values narrower than 32 bits are extended to `I32`, or to the widest integer type that
+    /// fits if the register is narrower, and wider values keep their width. Returns `None` if
+    /// this isn't a scalar integer type or the value doesn't fit in the register.
+    pub fn widen_to_register(self, reg_bits: u32) -> Option<Self> {
+        if !self.is_int() || self.bits() > reg_bits {
+            return None;
+        }
+        let limit = reg_bits.min(32);
+        let mut ty = self;
+        while let Some(wider) = ty.wider_int().filter(|wider| wider.bits() <= limit) {
+            ty = wider;
+        }
+        Some(ty)
+    }
+
+    /// Can a value of this type be accessed with a single native atomic operation on a target
+    /// with `ptr_bits`-bit pointers?
+    ///
+    /// This is true for integer and float lane types no wider than a pointer, and false for
+    /// vectors and `INVALID`.
+    pub const fn is_atomic_capable(self, ptr_bits: u32) -> bool {
+        (self.is_int() || self.is_float())
+            && self.bytes().is_power_of_two()
+            && self.bits() <= ptr_bits
+    }
+
+    /// Does a value of this type fit in a single scalar register of `reg_bits` bits?
+    ///
+    /// Unlike [`Self::fits_in_register`], this is false for vector types, which live in vector
+    /// registers regardless of their size.
+    pub const fn fits_scalar_register(self, reg_bits: u32) -> bool {
+        self.is_lane() && self.fits_in_register(reg_bits)
+    }
+
+    /// Get the natural alignment in bytes of a value of this type in memory.
+    ///
+    /// Scalars are aligned to their size. Vectors are aligned to their size, but no more than
+    /// 16 bytes, which is the widest alignment required for vector loads and stores on the
+    /// common targets. Types with no size, such as `INVALID`, are given an alignment of 1.
+    pub fn minimum_alignment(self) -> u32 {
+        self.bytes().clamp(1, 16)
+    }
+
+    /// Round `offset` up to the alignment of this type, as given by
+    /// [`Self::minimum_alignment`], such as when laying out the fields of a struct.
+    ///
+    /// Returns `None` if the aligned offset doesn't fit in a `u32`.
+    pub fn align_offset(self, offset: u32) -> Option<u32> {
+        offset.checked_next_multiple_of(self.minimum_alignment())
+    }
+
     /// Get a SIMD vector type with `n` times more lanes than this one.
     ///
     /// If this is a scalar type, this produces a SIMD type with this as a lane type and `n` lanes.
     ///
     /// If this is already a SIMD vector type, this produces a SIMD vector type with `n *
     /// self.lane_count()` lanes.
-    pub fn by(self, n: u32) -> Option<Self> {
+    ///
+    /// Returns `None` in the cases where [`Self::checked_by`] returns an error.
+    pub const fn by(self, n: u32) -> Option<Self> {
+        match self.checked_by(n) {
+            Ok(ty) => Some(ty),
+            Err(_) => None,
+        }
+    }
+
+    /// Get a SIMD vector type with `n` times more lanes than this one, reporting why that isn't
+    /// possible on failure.
+    pub const fn checked_by(self, n: u32) -> Result<Self, TypeError> {
         if self.is_dynamic_vector() {
-            return None;
+            return Err(TypeError::DynamicVector);
         }
-        if self.lane_bits() == 0 || !n.is_power_of_two() {
-            return None;
+        if self.lane_bits() == 0 {
+            return Err(TypeError::InvalidLane);
+        }
+        if !n.is_power_of_two() {
+            return Err(TypeError::NonPowerOfTwoLanes);
         }
         let log2_lanes: u32 = n.trailing_zeros();
-        let new_type = u32::from(self.0) + (log2_lanes << 4);
+        let new_type = self.0 as u32 + (log2_lanes << 4);
         if new_type < constants::DYNAMIC_VECTOR_BASE as u32
             && (new_type as u16) < constants::DYNAMIC_VECTOR_BASE
         {
-            Some(Self(new_type as u16))
+            Ok(Self(new_type as u16))
         } else {
-            None
+            Err(TypeError::TooManyLanes)
+        }
+    }
+
+    /// Like [`Self::by`], but clamps the result to the maximum of 256 lanes instead of failing
+    /// when there would be too many lanes.
+    ///
+    /// Still returns `None` for the other errors reported by [`Self::checked_by`], such as a
+    /// non-power-of-two `n` or an `INVALID` lane type.
+    pub const fn saturating_by(self, n: u32) -> Option<Self> {
+        match self.checked_by(n) {
+            Ok(ty) => Some(ty),
+            // 256 lanes is `log2_lane_count` 8.
+            Err(TypeError::TooManyLanes) => Some(Self(self.lane_type().0 + (8 << 4))),
+            Err(_) => None,
+        }
+    }
+
+    /// Build a SIMD vector type with `count` lanes of type `lane`.
+    ///
+    /// Unlike [`Self::checked_by`], `lane` must be a lane type rather than an existing vector,
+    /// which makes this suitable for building types from parsed data.
+    pub const fn vector_of(lane: Self, count: u32) -> Result<Self, TypeError> {
+        if !lane.is_simd_lane_legal() {
+            return Err(TypeError::InvalidLane);
+        }
+        lane.checked_by(count)
+    }
+
+    /// Get the largest lane count `n` such that a vector of `n` lanes of this type's lane type
+    /// fits in `max_bits`.
+    ///
+    /// The result is a power of two no larger than 256, or 0 if even a single lane doesn't fit.
+    pub fn max_lanes_within(self, max_bits: u32) -> u32 {
+        let lane_bits = self.lane_bits();
+        if lane_bits == 0 || lane_bits > max_bits {
+            return 0;
+        }
+        let lanes = max_bits / lane_bits;
+        (1 << lanes.ilog2()).min(256)
+    }
+
+    /// Get a SIMD vector type with half the number of lanes.
+    ///
+    /// Returns `None` for scalar types, which can't be halved, and for dynamic vector types.
+    pub const fn half_vector(self) -> Option<Self> {
+        match self.checked_half_vector() {
+            Ok(ty) => Some(ty),
+            Err(_) => None,
+        }
+    }
+
+    /// Get a SIMD vector type with half the number of lanes, reporting why that isn't possible
+    /// on failure.
+    pub const fn checked_half_vector(self) -> Result<Self, TypeError> {
+        if self.is_dynamic_vector() {
+            Err(TypeError::DynamicVector)
+        } else if self.is_vector() {
+            Ok(Self(self.0 - 0x10))
+        } else {
+            Err(TypeError::NotAVector)
         }
     }
 
+    /// Get a SIMD vector type with twice the number of lanes.
+    ///
+    /// This is the inverse of [`Self::half_vector`] and is equivalent to `self.by(2)`, so a
+    /// scalar type becomes a two-lane vector. Returns `None` if the result would have more than
+    /// 256 lanes.
+    pub const fn double_vector(self) -> Option<Self> {
+        self.by(2)
+    }
+
+    /// Iterate over this type followed by every SIMD vector type with the same lane type and
+    /// more lanes, doubling the lane count each step up to the maximum of 256 lanes.
+    ///
+    /// For types that can't be made wider, such as dynamic vectors, this only yields `self`.
+    pub fn all_lanes(self) -> impl Iterator<Item = Type> {
+        core::iter::successors(Some(self), |ty| ty.double_vector())
+    }
+
     /// Convert a fixed vector type to a dynamic one.
+    ///
+    /// Panics if this isn't a fixed vector type.
     pub fn vector_to_dynamic(self) -> Option<Self> {
-        assert!(self.is_vector());
+        // The messages use `Debug` because `Display` panics on malformed types.
+        assert!(
+            self.is_vector(),
+            "vector_to_dynamic requires a fixed vector type, got {self:?}"
+        );
         if self.bits() > 256 {
             return None;
         }
         let new_ty = self.0 + constants::VECTOR_BASE;
         let ty = Some(Self(new_ty));
-        assert!(ty.unwrap().is_dynamic_vector());
+        assert!(
+            ty.unwrap().is_dynamic_vector(),
+            "{self:?} converted to non-dynamic Type(0x{new_ty:x})"
+        );
         return ty;
     }
 
     /// Convert a dynamic vector type to a fixed one.
+    ///
+    /// Panics if this isn't a dynamic vector type.
     pub fn dynamic_to_vector(self) -> Option<Self> {
-        assert!(self.is_dynamic_vector());
+        assert!(
+            self.is_dynamic_vector(),
+            "dynamic_to_vector requires a dynamic vector type, got {self:?}"
+        );
         Some(Self(self.0 - constants::VECTOR_BASE))
     }
 
+    /// Get the result type of a widening multiply of integer vectors of this type, which has
+    /// lanes that are twice as wide and half as many lanes, such as `I32X4` for `I16X8`.
+    ///
+    /// This is [`Self::merge_lanes`] restricted to integer lanes. Returns `None` for scalars,
+    /// dynamic vectors, `I128` lanes and types without integer lanes.
+    pub fn widening_mul_result(self) -> Option<Self> {
+        if self.lane_type().is_int() {
+            self.merge_lanes()
+        } else {
+            None
+        }
+    }
+
+    /// Get the types of the low and high halves of this scalar integer type, as produced by
+    /// `isplit`, such as `(I32, I32)` for `I64`.
+    ///
+    /// Returns `None` for `I8`, which can't be split, and for anything but scalar integers.
+    pub fn split_pair(self) -> Option<(Self, Self)> {
+        if !self.is_int() {
+            return None;
+        }
+        let half = self.narrower_int()?;
+        Some((half, half))
+    }
+
+    /// Get the type of two values of this scalar integer type concatenated with `iconcat`,
+    /// such as `I128` for `I64`.
+    ///
+    /// This is the inverse of [`Self::split_pair`]. Returns `None` for `I128` and for anything
+    /// but scalar integers.
+    pub fn concat_pair(self) -> Option<Self> {
+        if self.is_int() {
+            self.wider_int()
+        } else {
+            None
+        }
+    }
+
+    /// Get the integer type with `lane_bits`-bit lanes and the same total number of bits as this
+    /// type, such as `I32X4` for `I8X16` and 32-bit lanes.
+    ///
+    /// Returns `None` if there is no `lane_bits`-bit integer type, if the bits don't divide
+    /// evenly into lanes, or for types without a fixed size.
+    pub fn reshape_to_lane_bits(self, lane_bits: u32) -> Option<Self> {
+        let lane = Type::int(u16::try_from(lane_bits).ok()?)?;
+        let bits = self.bits();
+        if bits == 0 || bits % lane_bits != 0 {
+            return None;
+        }
+        lane.by(bits / lane_bits)
+    }
+
     /// Split the lane width in half and double the number of lanes to maintain the same bit-width.
     ///
     /// If this is a scalar type of `n` bits, it produces a SIMD vector type of `(n/2)x2`.
@@ -382,6 +1016,20 @@ impl Type {
         }
     }
 
+    /// Get the smallest integer type that can hold any lane index of this type.
+    ///
+    /// SIMD vectors have at most 256 lanes, so this is currently always `I8`. It is computed
+    /// from the lane count rather than hard-coded so that callers keep working if wider vectors
+    /// are ever supported. Scalar types have a single lane and dynamic vectors use their
+    /// minimum lane count.
+    pub fn index_type(self) -> Self {
+        let max_index = self.min_lane_count().saturating_sub(1);
+        let bits = (u32::BITS - max_index.leading_zeros())
+            .max(8)
+            .next_power_of_two();
+        Self::int(bits as u16).unwrap()
+    }
+
     /// Index of this type, for use with hash tables etc.
     pub fn index(self) -> usize {
         usize::from(self.0)
@@ -395,7 +1043,247 @@ impl Type {
         self.lane_count() == other.lane_count() && self.lane_bits() >= other.lane_bits()
     }
 
-    /// Return the pointer type for the given target triple.
+    /// Can a value of this type be reinterpreted as `other` with a bitcast?
+    ///
+    /// This is true when both types have the same, non-zero, number of bits. Dynamic vectors
+    /// have no fixed size and are never bit-compatible.
+    pub const fn bit_compatible(self, other: Self) -> bool {
+        self.bits() != 0 && self.bits() == other.bits()
+    }
+
+    /// Do `self` and `other` have the same number of lanes?
+    ///
+    /// Dynamic vectors only have the same number of lanes as other dynamic vectors with the
+    /// same minimum lane count.
+    pub const fn same_lane_count(self, other: Self) -> bool {
+        self.is_dynamic_vector() == other.is_dynamic_vector()
+            && self.min_lane_count() == other.min_lane_count()
+    }
+
+    /// Do `self` and `other` have the same lane type, regardless of the number of lanes?
+    ///
+    /// For example `I32`, `I32X8` and `I32X4XN` all have the same lane type.
+    pub const fn same_lane_type(self, other: Self) -> bool {
+        self.lane_type().0 == other.lane_type().0
+    }
+
+    /// Get a type with the lane type of `self` and the number of lanes of `other`.
+    ///
+    /// For example, `I8.with_lane_count_of(I32X4)` is `I8X4`. Returns `None` if either type is
+    /// a dynamic vector or if `self` has no valid lane type.
+    pub const fn with_lane_count_of(self, other: Self) -> Option<Self> {
+        if self.is_dynamic_vector() {
+            return None;
+        }
+        self.lane_type().by(other.lane_count())
+    }
+
+    /// Find the type that both `self` and `other` can be widened to, for two-operand
+    /// operations.
+    ///
+    /// Returns the type with the wider lanes if both types have integer lanes or both have
+    /// floating point lanes, and both have the same number of lanes. Otherwise returns `None`.
+    pub fn common(self, other: Self) -> Option<Self> {
+        let (a, b) = (self.lane_type(), other.lane_type());
+        let same_category = (a.is_int() && b.is_int()) || (a.is_float() && b.is_float());
+        if !same_category || !self.same_lane_count(other) {
+            None
+        } else if self.lane_bits() >= other.lane_bits() {
+            Some(self)
+        } else {
+            Some(other)
+        }
+    }
+
+    /// Get the result type of a lane-wise binary operation such as `iadd` with operands of
+    /// type `self` and `other`.
+    ///
+    /// Both operands must have the same type, which can't be `INVALID`.
+    pub fn binop_result(self, other: Self) -> Result<Self, TypeError> {
+        if self == other && !self.is_invalid() {
+            Ok(self)
+        } else {
+            Err(TypeError::Mismatch {
+                lhs: self,
+                rhs: other,
+            })
+        }
+    }
+
+    /// Get a structured description of this type.
+    pub fn describe(self) -> TypeInfo {
+        let lane = self.lane_type();
+        let category = if lane.is_int() {
+            TypeCategory::Int
+        } else if lane.is_float() {
+            TypeCategory::Float
+        } else {
+            TypeCategory::Invalid
+        };
+        TypeInfo {
+            category,
+            lane_bits: self.lane_bits(),
+            lane_count: self.min_lane_count(),
+            dynamic: self.is_dynamic_vector(),
+        }
+    }
+
+    /// Classify how a value of this type is passed, assuming vector registers of 128 bits.
+    ///
+    /// See [`Self::abi_class_with_threshold`].
+    pub fn abi_class(self) -> Option<AbiClass> {
+        self.abi_class_with_threshold(128)
+    }
+
+    /// Classify how a value of this type is passed, when vector registers have
+    /// `vector_reg_bits` bits.
+    ///
+    /// Integers of up to 64 bits are passed in integer registers, and floats and fixed vectors
+    /// of up to `vector_reg_bits` bits in float or vector registers. Anything wider, including
+    /// `I128`, is passed in memory. Dynamic vectors are always sized to fit a vector register.
+    ///
+    /// Returns `None` for `INVALID`, which has no values to pass.
+    pub fn abi_class_with_threshold(self, vector_reg_bits: u32) -> Option<AbiClass> {
+        let lane = self.lane_type();
+        if !lane.is_int() && !lane.is_float() {
+            return None;
+        }
+        let class = if self.is_dynamic_vector() {
+            AbiClass::FloatVector
+        } else if self.is_int() && self.bits() <= 64 {
+            AbiClass::Integer
+        } else if !self.is_int() && self.bits() <= vector_reg_bits {
+            AbiClass::FloatVector
+        } else {
+            AbiClass::Memory
+        };
+        Some(class)
+    }
+
+    /// Get the DWARF base type encoding (`DW_ATE_*`) and byte size of this scalar type, for
+    /// emitting debug info.
+    ///
+    /// Integers are described as `DW_ATE_signed`, since Cranelift integers have no signedness,
+    /// and floats as `DW_ATE_float`. Returns `None` for `INVALID` and vectors, which need to be
+    /// described as composite types.
+    pub const fn dwarf_base_type(self) -> Option<(u8, u16)> {
+        const DW_ATE_FLOAT: u8 = 0x04;
+        const DW_ATE_SIGNED: u8 = 0x05;
+        let encoding = if self.is_int() {
+            DW_ATE_SIGNED
+        } else if self.is_float() {
+            DW_ATE_FLOAT
+        } else {
+            return None;
+        };
+        Some((encoding, self.bytes() as u16))
+    }
+
+    /// Get the name of the equivalent LLVM IR type, such as `i32`, `double` or `<4 x i32>`.
+    ///
+    /// `INVALID` is mapped to `void`, and dynamic vectors to scalable vectors such as
+    /// `<vscale x 4 x i32>`. Returns `None` for malformed types.
+    pub fn llvm_name(self) -> Option<String> {
+        let lane = match self.lane_type() {
+            I8 | I16 | I32 | I64 | I128 => format!("i{}", self.lane_bits()),
+            F16 => "half".to_string(),
+            F32 => "float".to_string(),
+            F64 => "double".to_string(),
+            F128 => "fp128".to_string(),
+            INVALID => return Some("void".to_string()),
+            _ => return None,
+        };
+        Some(if self.is_dynamic_vector() {
+            format!("<vscale x {} x {lane}>", self.min_lane_count())
+        } else if self.is_vector() {
+            format!("<{} x {lane}>", self.lane_count())
+        } else {
+            lane
+        })
+    }
+
+    /// Get the WebAssembly value type that values of this type are represented as.
+    ///
+    /// WebAssembly has no integers narrower than 32 bits, so `I8` and `I16` values are
+    /// represented as `i32`. Every 128-bit fixed vector is a `v128`. Returns `None` for all
+    /// other types, such as `I128`, `F16` and vectors of other sizes.
+    pub fn to_wasm_valtype(self) -> Option<WasmValType> {
+        match self {
+            I8 | I16 | I32 => Some(WasmValType::I32),
+            I64 => Some(WasmValType::I64),
+            F32 => Some(WasmValType::F32),
+            F64 => Some(WasmValType::F64),
+            _ if self.is_vector() && self.bits() == 128 => Some(WasmValType::V128),
+            _ => None,
+        }
+    }
+
+    /// Get the type used for values of the WebAssembly value type `w`.
+    ///
+    /// A `v128` is represented as `I8X16`, the same as the other 128-bit vector types by
+    /// bitcasting.
+    pub const fn from_wasm_valtype(w: WasmValType) -> Self {
+        match w {
+            WasmValType::I32 => I32,
+            WasmValType::I64 => I64,
+            WasmValType::F32 => F32,
+            WasmValType::F64 => F64,
+            WasmValType::V128 => I8X16,
+        }
+    }
+
+    /// Pair this type with signed interpretation of its integer lanes.
+    pub fn to_signed(self) -> SignedType {
+        SignedType::new(self, Signedness::Signed)
+    }
+
+    /// Pair this type with unsigned interpretation of its integer lanes.
+    pub fn to_unsigned(self) -> SignedType {
+        SignedType::new(self, Signedness::Unsigned)
+    }
+
+    /// Compare two types in the canonical order, which is suitable for sorting types when
+    /// emitting tables and other output that must be deterministic:
+    ///
+    /// 1. `INVALID` comes first, followed by fixed-size types and then dynamic vector types.
+    /// 2. Integer lane types come before floating point lane types.
+    /// 3. Narrower lanes come before wider lanes.
+    /// 4. Fewer lanes come before more lanes, using the minimum lane count for dynamic vectors.
+    ///
+    /// This order doesn't depend on the bit-level representation of the type and won't
+    /// change, even if the `Ord` implementation does.
+    pub fn cmp_canonical(self, other: Self) -> Ordering {
+        let key = |ty: Type| {
+            let lane = ty.lane_type();
+            let category = if lane.is_int() {
+                1
+            } else if lane.is_float() {
+                2
+            } else {
+                0
+            };
+            (
+                ty.is_dynamic_vector(),
+                category,
+                ty.lane_bits(),
+                ty.min_lane_count(),
+                // Break ties between malformed types so the order stays total.
+                ty.0,
+            )
+        };
+        key(self).cmp(&key(other))
+    }
+
+    /// Compare two types by size, with the largest first, such as for packing larger values
+    /// first.
+    ///
+    /// Types of the same size, including the dynamic vectors whose size is unknown, are
+    /// ordered by [`Self::cmp_canonical`].
+    pub fn cmp_by_size(a: Self, b: Self) -> Ordering {
+        b.bits().cmp(&a.bits()).then_with(|| a.cmp_canonical(b))
+    }
+
+    /// Return the pointer type for the given target triple.
     pub fn triple_pointer_type(triple: &Triple) -> Self {
         match triple.pointer_width() {
             Ok(PointerWidth::U16) => I16,
@@ -405,134 +1293,1688 @@ impl Type {
         }
     }
 
-    /// Gets a bit-level representation of the type. Used only
-    /// internally for efficiently storing types.
-    pub(crate) fn repr(self) -> u16 {
-        self.0
+    /// Gets a bit-level representation of the type.
+    ///
+    /// The representation can be converted back with [`Self::try_from_repr`].
+    pub const fn repr(self) -> u16 {
+        self.0
+    }
+
+    /// Get the lane type code, which is the low 4 bits of [`Self::repr`].
+    ///
+    /// All types with the same lane type share the same code, such as 0x6 for `I32`, `I32X4`
+    /// and `I32X4XN`.
+    pub const fn scalar_code(self) -> u8 {
+        (self.0 & 0x0f) as u8
+    }
+
+    /// Get the bits of [`Self::repr`] above the lane type code.
+    ///
+    /// This is 0x7 for lane types, 0x7 plus the log2 of the lane count for fixed vectors and
+    /// 0xf plus the log2 of the minimum lane count for dynamic vectors.
+    pub const fn lane_shift(self) -> u8 {
+        (self.0 >> 4) as u8
+    }
+
+    /// Converts from a bit-level representation of the type back to a
+    /// `Type`. Used only internally for efficiently storing types.
+    pub(crate) fn from_repr(bits: u16) -> Type {
+        Type(bits)
+    }
+
+    /// Converts from a bit-level representation of the type back to a `Type`, checking that
+    /// `bits` is the representation of `INVALID` or a lane, vector or dynamic vector type.
+    ///
+    /// Returns `None` for any bit pattern that isn't a valid type.
+    pub fn try_from_repr(bits: u16) -> Option<Type> {
+        let ty = Type(bits);
+        if ty.is_invalid() || ty.is_canonical() {
+            Some(ty)
+        } else {
+            None
+        }
+    }
+
+    /// Is this a well-formed lane, vector or dynamic vector type?
+    ///
+    /// This is true for every type produced by the constants and constructors in this module
+    /// other than `INVALID`, and false for malformed bit patterns.
+    pub const fn is_canonical(self) -> bool {
+        let fixed = if self.is_dynamic_vector() {
+            if self.0 >= constants::DYNAMIC_VECTOR_BASE + constants::VECTOR_BASE {
+                return false;
+            }
+            Type(self.0 - constants::VECTOR_BASE)
+        } else {
+            self
+        };
+        fixed.lane_type().lane_bits() != 0
+    }
+
+    /// Get the name of the equivalent portable C type, such as `int32_t` for `I32`, for
+    /// generating C declarations.
+    ///
+    /// Integers are always mapped to the signed `intN_t` types, since Cranelift integers have
+    /// no signedness. `INVALID` is mapped to `void`. Returns `None` for types without a
+    /// portable C equivalent, such as `I128`, `F16`, `F128` and vectors.
+    pub const fn c_name(self) -> Option<&'static str> {
+        match self {
+            INVALID => Some("void"),
+            I8 => Some("int8_t"),
+            I16 => Some("int16_t"),
+            I32 => Some("int32_t"),
+            I64 => Some("int64_t"),
+            F32 => Some("float"),
+            F64 => Some("double"),
+            _ => None,
+        }
+    }
+
+    /// Write the textual name of this type to `w`, without allocating.
+    ///
+    /// This writes the same text as the `Display` implementation.
+    pub fn write_name(self, w: &mut impl fmt::Write) -> fmt::Result {
+        write!(w, "{self}")
+    }
+
+    /// Does the textual name of this type match `pat`?
+    ///
+    /// The pattern is matched against the `Display` form of the type, and may start or end with
+    /// a `*` wildcard: `i*` matches all integer types, `*x4` all four-lane vectors and `*` every
+    /// type. Returns `false` for `INVALID` and malformed types.
+    pub fn matches_pattern(self, pat: &str) -> bool {
+        if !self.is_canonical() {
+            return false;
+        }
+        let name = self.to_string();
+        let (pat, any_prefix) = match pat.strip_prefix('*') {
+            Some(rest) => (rest, true),
+            None => (pat, false),
+        };
+        let (pat, any_suffix) = match pat.strip_suffix('*') {
+            Some(rest) => (rest, true),
+            None => (pat, false),
+        };
+        match (any_prefix, any_suffix) {
+            (false, false) => name == pat,
+            (true, false) => name.ends_with(pat),
+            (false, true) => name.starts_with(pat),
+            (true, true) => name.contains(pat),
+        }
+    }
+
+    /// Parse a type name in the format produced by `Display`, such as `i32` or `f64x2`.
+    ///
+    /// This is the same as the `FromStr` implementation, but can be used in constants. See also
+    /// the [`typ!`](crate::typ) macro.
+    pub const fn from_name(name: &str) -> Result<Self, ParseTypeError> {
+        let bytes = name.as_bytes();
+        let mut split = 0;
+        while split < bytes.len() && bytes[split] != b'x' {
+            split += 1;
+        }
+        let (lane, count) = bytes.split_at(split);
+        let lane = match lane {
+            b"i8" => I8,
+            b"i16" => I16,
+            b"i32" => I32,
+            b"i64" => I64,
+            b"i128" => I128,
+            b"f16" => F16,
+            b"f32" => F32,
+            b"f64" => F64,
+            b"f128" => F128,
+            _ => return Err(ParseTypeError::UnknownLaneType),
+        };
+        if count.is_empty() {
+            return Ok(lane);
+        }
+        // Only accept the lane counts `Display` produces, so reject a missing count and leading
+        // zeros.
+        if count.len() == 1 || count[1] == b'0' {
+            return Err(ParseTypeError::InvalidLaneCount);
+        }
+        // Skip the `x` and parse the decimal lane count.
+        let mut n = 0u32;
+        let mut i = 1;
+        while i < count.len() {
+            if !count[i].is_ascii_digit() {
+                return Err(ParseTypeError::InvalidLaneCount);
+            }
+            n = n * 10 + (count[i] - b'0') as u32;
+            // No vector has more than 256 lanes, so stop before the count can overflow.
+            if n > 256 {
+                return Err(ParseTypeError::InvalidLaneCount);
+            }
+            i += 1;
+        }
+        // A single lane is spelled as the lane type itself.
+        if n < 2 {
+            return Err(ParseTypeError::InvalidLaneCount);
+        }
+        match lane.by(n) {
+            Some(ty) => Ok(ty),
+            None => Err(ParseTypeError::InvalidLaneCount),
+        }
+    }
+
+    /// Append a compact byte encoding of this type to `out`.
+    ///
+    /// The encoding is the LEB128 encoding of [`Self::repr`], so it stays readable if the
+    /// representation grows. It can be read back with [`Self::decode`].
+    pub fn encode(self, out: &mut Vec<u8>) {
+        let mut value = self.0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    /// Read a type written by [`Self::encode`] from the start of `input`.
+    ///
+    /// Returns the type and the number of bytes consumed. Representations rejected by
+    /// [`Self::try_from_repr`] are reported as [`TypeError::InvalidRepr`].
+    pub fn decode(input: &[u8]) -> Result<(Type, usize), TypeError> {
+        let mut value = 0u32;
+        // A `u16` needs at most 3 LEB128 bytes.
+        for (i, &byte) in input.iter().enumerate().take(3) {
+            value |= u32::from(byte & 0x7f) << (7 * i);
+            if byte & 0x80 == 0 {
+                // `encode` never ends with a zero continuation byte, so reject overlong
+                // encodings to keep each type's encoding unique.
+                if i > 0 && byte == 0 {
+                    return Err(TypeError::InvalidEncoding);
+                }
+                let bits = u16::try_from(value).map_err(|_| TypeError::InvalidEncoding)?;
+                return Ok((Type::try_from(bits)?, i + 1));
+            }
+        }
+        Err(TypeError::InvalidEncoding)
+    }
+}
+
+/// Types are ordered by [`Type::cmp_canonical`], so that related types cluster together.
+impl Ord for Type {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_canonical(*other)
+    }
+}
+
+impl PartialOrd for Type {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Type {
+    /// Print the textual name of the type, such as `i32` or `f64x2`.
+    ///
+    /// The alternate form (`{:#}`) also prints the bit-level representation, such as
+    /// `i32 (0x76)`, and prints `invalid(0x..)` rather than panicking for invalid types.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if f.alternate() {
+            return if self.is_canonical() {
+                write!(f, "{self} ({:#04x})", self.0)
+            } else {
+                write!(f, "invalid({:#04x})", self.0)
+            };
+        }
+        // Types are printed often, so look up the lane type name from the low 4 bits instead of
+        // going through the lane type predicates.
+        let lane_name = if self.0 >= constants::LANE_BASE {
+            LANE_NAMES[usize::from(self.0 & 0x0f)]
+        } else {
+            ""
+        };
+        if lane_name.is_empty() {
+            match *self {
+                INVALID => panic!("INVALID encountered"),
+                _ => panic!("Unknown Type(0x{:x})", self.0),
+            }
+        } else if self.is_dynamic_vector() {
+            write!(f, "{lane_name}x{}xN", self.min_lane_count())
+        } else if self.is_vector() {
+            write!(f, "{lane_name}x{}", self.lane_count())
+        } else {
+            f.write_str(lane_name)
+        }
+    }
+}
+
+/// Names of the lane types, indexed by the low 4 bits of their representation. Unused codes
+/// have an empty name.
+const LANE_NAMES: [&str; 16] = [
+    "", "", "", "", "i8", "i16", "i32", "i64", "i128", "f16", "f32", "f64", "f128", "", "", "",
+];
+
+// `LANE_NAMES` must put each lane type's name at the index of its low 4 bits.
+const _: () = assert!(I8.0 & 0x0f == 4);
+const _: () = assert!(I16.0 & 0x0f == 5);
+const _: () = assert!(I32.0 & 0x0f == 6);
+const _: () = assert!(I64.0 & 0x0f == 7);
+const _: () = assert!(I128.0 & 0x0f == 8);
+const _: () = assert!(F16.0 & 0x0f == 9);
+const _: () = assert!(F32.0 & 0x0f == 10);
+const _: () = assert!(F64.0 & 0x0f == 11);
+const _: () = assert!(F128.0 & 0x0f == 12);
+
+impl Debug for Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_int() {
+            write!(f, "types::I{}", self.lane_bits())
+        } else if self.is_float() {
+            write!(f, "types::F{}", self.lane_bits())
+        } else if self.is_vector() {
+            write!(f, "{:?}X{}", self.lane_type(), self.lane_count())
+        } else if self.is_dynamic_vector() {
+            write!(f, "{:?}X{}XN", self.lane_type(), self.min_lane_count())
+        } else {
+            match *self {
+                INVALID => write!(f, "types::INVALID"),
+                _ => write!(f, "Type(0x{:x})", self.0),
+            }
+        }
+    }
+}
+
+/// An error produced when a [`Type`] can't be constructed from another type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeError {
+    /// The lane type is `INVALID` or otherwise can't be used as the lane of a SIMD vector.
+    InvalidLane,
+
+    /// The requested number of lanes is not a power of two.
+    NonPowerOfTwoLanes,
+
+    /// The resulting vector would have more than 256 lanes.
+    TooManyLanes,
+
+    /// The operation isn't supported for dynamic vector types.
+    DynamicVector,
+
+    /// The operation requires a SIMD vector type.
+    NotAVector,
+
+    /// The bit-level representation doesn't correspond to a valid type.
+    InvalidRepr(u16),
+
+    /// The byte encoding is truncated, overlong, or too long to be a type representation.
+    InvalidEncoding,
+
+    /// The operands of an instruction don't have the same type.
+    Mismatch {
+        /// The type of the left-hand operand.
+        lhs: Type,
+        /// The type of the right-hand operand.
+        rhs: Type,
+    },
+}
+
+// This is manually implementing Error and Display instead of using thiserror to reduce the amount
+// of dependencies used by Cranelift.
+impl std::error::Error for TypeError {}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TypeError::InvalidLane => write!(f, "invalid SIMD lane type"),
+            TypeError::NonPowerOfTwoLanes => write!(f, "SIMD lane count is not a power of two"),
+            TypeError::TooManyLanes => write!(f, "no more than 256 SIMD lanes supported"),
+            TypeError::DynamicVector => write!(f, "unsupported for dynamic vector types"),
+            TypeError::NotAVector => write!(f, "not a SIMD vector type"),
+            TypeError::InvalidRepr(bits) => write!(f, "invalid type representation {bits:#x}"),
+            TypeError::InvalidEncoding => write!(f, "invalid type encoding"),
+            // Use `Debug` since either operand may be `INVALID`, which `Display` rejects.
+            TypeError::Mismatch { lhs, rhs } => {
+                write!(f, "operand types {lhs:?} and {rhs:?} don't match")
+            }
+        }
+    }
+}
+
+/// An error produced when parsing a [`Type`] from its textual name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseTypeError {
+    /// The lane type name is not a known type, such as `i32` or `f64`.
+    UnknownLaneType,
+
+    /// The lane count is not a power of two, or it produces a vector with too many lanes.
+    InvalidLaneCount,
+}
+
+impl std::error::Error for ParseTypeError {}
+
+impl Display for ParseTypeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseTypeError::UnknownLaneType => write!(f, "unknown lane type"),
+            ParseTypeError::InvalidLaneCount => write!(f, "invalid SIMD lane count"),
+        }
+    }
+}
+
+impl FromStr for Type {
+    type Err = ParseTypeError;
+
+    /// Parse a type name in the format produced by `Display`, such as `i32` or `f64x2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Type::from_name(s)
+    }
+}
+
+/// The default type is `INVALID`, the "no type" value.
+impl Default for Type {
+    fn default() -> Self {
+        INVALID
+    }
+}
+
+/// Compare a type against its textual name, such as `"i32"`. Strings that aren't valid type
+/// names compare unequal.
+impl PartialEq<str> for Type {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<Type>() == Ok(*self)
+    }
+}
+
+impl PartialEq<&str> for Type {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl TryFrom<u16> for Type {
+    type Error = TypeError;
+
+    fn try_from(bits: u16) -> Result<Self, Self::Error> {
+        Type::try_from_repr(bits).ok_or(TypeError::InvalidRepr(bits))
+    }
+}
+
+impl From<Type> for u16 {
+    fn from(ty: Type) -> Self {
+        ty.repr()
+    }
+}
+
+/// A description of a [`Type`] that is stable across releases, for exchanging types between
+/// processes.
+///
+/// The bit-level representation of `Type` is an implementation detail that may change, but the
+/// discriminants of this enum are fixed. They are the tags of the byte encoding written by
+/// [`WireType::to_bytes`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum WireType {
+    /// The `INVALID` type.
+    Invalid = 0,
+    /// An 8-bit integer.
+    I8 = 1,
+    /// A 16-bit integer.
+    I16 = 2,
+    /// A 32-bit integer.
+    I32 = 3,
+    /// A 64-bit integer.
+    I64 = 4,
+    /// A 128-bit integer.
+    I128 = 5,
+    /// A 16-bit floating point number.
+    F16 = 6,
+    /// A 32-bit floating point number.
+    F32 = 7,
+    /// A 64-bit floating point number.
+    F64 = 8,
+    /// A 128-bit floating point number.
+    F128 = 9,
+    /// A SIMD vector with `1 << log2_count` lanes.
+    Vector {
+        /// The type of each lane.
+        lane: Box<WireType>,
+        /// The base 2 logarithm of the number of lanes.
+        log2_count: u8,
+    } = 16,
+    /// A dynamic SIMD vector with at least `1 << log2_min_count` lanes.
+    DynamicVector {
+        /// The type of each lane.
+        lane: Box<WireType>,
+        /// The base 2 logarithm of the minimum number of lanes.
+        log2_min_count: u8,
+    } = 17,
+}
+
+impl WireType {
+    /// Encode this description as bytes.
+    ///
+    /// Each variant is written as its discriminant. Vectors follow it with their lane count and
+    /// then the encoding of their lane type. The encoding can be read back with
+    /// [`Self::from_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut wire = self;
+        loop {
+            let tag = match wire {
+                WireType::Invalid => 0,
+                WireType::I8 => 1,
+                WireType::I16 => 2,
+                WireType::I32 => 3,
+                WireType::I64 => 4,
+                WireType::I128 => 5,
+                WireType::F16 => 6,
+                WireType::F32 => 7,
+                WireType::F64 => 8,
+                WireType::F128 => 9,
+                WireType::Vector { lane, log2_count } => {
+                    out.extend([16, *log2_count]);
+                    wire = lane;
+                    continue;
+                }
+                WireType::DynamicVector {
+                    lane,
+                    log2_min_count,
+                } => {
+                    out.extend([17, *log2_min_count]);
+                    wire = lane;
+                    continue;
+                }
+            };
+            out.push(tag);
+            return out;
+        }
+    }
+
+    /// Read a description written by [`Self::to_bytes`] from the start of `bytes`.
+    ///
+    /// Returns the description and the number of bytes consumed. Vectors whose lanes are
+    /// vectors don't describe any type, so they are rejected with [`TypeError::InvalidLane`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), TypeError> {
+        let scalar = |tag: Option<&u8>| {
+            Ok(match tag {
+                Some(0) => WireType::Invalid,
+                Some(1) => WireType::I8,
+                Some(2) => WireType::I16,
+                Some(3) => WireType::I32,
+                Some(4) => WireType::I64,
+                Some(5) => WireType::I128,
+                Some(6) => WireType::F16,
+                Some(7) => WireType::F32,
+                Some(8) => WireType::F64,
+                Some(9) => WireType::F128,
+                Some(16 | 17) => return Err(TypeError::InvalidLane),
+                _ => return Err(TypeError::InvalidEncoding),
+            })
+        };
+        match bytes {
+            [tag @ (16 | 17), log2_count, rest @ ..] => {
+                let lane = Box::new(scalar(rest.first())?);
+                let wire = if *tag == 16 {
+                    WireType::Vector {
+                        lane,
+                        log2_count: *log2_count,
+                    }
+                } else {
+                    WireType::DynamicVector {
+                        lane,
+                        log2_min_count: *log2_count,
+                    }
+                };
+                Ok((wire, 3))
+            }
+            [16 | 17] => Err(TypeError::InvalidEncoding),
+            _ => Ok((scalar(bytes.first())?, 1)),
+        }
+    }
+}
+
+impl From<Type> for WireType {
+    /// Convert a type to its stable description. Types with invalid representations become
+    /// [`WireType::Invalid`].
+    fn from(ty: Type) -> Self {
+        let lane = match ty.lane_type() {
+            I8 => WireType::I8,
+            I16 => WireType::I16,
+            I32 => WireType::I32,
+            I64 => WireType::I64,
+            I128 => WireType::I128,
+            F16 => WireType::F16,
+            F32 => WireType::F32,
+            F64 => WireType::F64,
+            F128 => WireType::F128,
+            _ => return WireType::Invalid,
+        };
+        if ty.is_vector() {
+            WireType::Vector {
+                lane: Box::new(lane),
+                log2_count: ty.log2_lane_count() as u8,
+            }
+        } else if ty.is_dynamic_vector() {
+            WireType::DynamicVector {
+                lane: Box::new(lane),
+                log2_min_count: ty.log2_min_lane_count() as u8,
+            }
+        } else {
+            lane
+        }
+    }
+}
+
+impl TryFrom<WireType> for Type {
+    type Error = TypeError;
+
+    fn try_from(wire: WireType) -> Result<Self, Self::Error> {
+        let vector = |lane: Box<WireType>, log2_count: u8| {
+            let lane = Type::try_from(*lane)?;
+            if !lane.is_simd_lane_legal() {
+                return Err(TypeError::InvalidLane);
+            }
+            let count = 1u32
+                .checked_shl(log2_count.into())
+                .ok_or(TypeError::TooManyLanes)?;
+            lane.checked_by(count)
+        };
+        Ok(match wire {
+            WireType::Invalid => INVALID,
+            WireType::I8 => I8,
+            WireType::I16 => I16,
+            WireType::I32 => I32,
+            WireType::I64 => I64,
+            WireType::I128 => I128,
+            WireType::F16 => F16,
+            WireType::F32 => F32,
+            WireType::F64 => F64,
+            WireType::F128 => F128,
+            WireType::Vector { lane, log2_count } => vector(lane, log2_count)?,
+            WireType::DynamicVector {
+                lane,
+                log2_min_count,
+            } => {
+                let fixed = vector(lane, log2_min_count)?;
+                // Dynamic vectors always have a minimum of at least two lanes.
+                if !fixed.is_vector() {
+                    return Err(TypeError::DynamicVector);
+                }
+                Type(fixed.0 + constants::VECTOR_BASE)
+            }
+        })
+    }
+}
+
+/// The kind of lanes a [`Type`] has, as reported by [`Type::describe`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeCategory {
+    /// `INVALID` or a type with an unknown lane type.
+    Invalid,
+
+    /// Integer lanes.
+    Int,
+
+    /// Floating point lanes.
+    Float,
+}
+
+/// A structured description of a [`Type`], as returned by [`Type::describe`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeInfo {
+    /// The kind of lanes.
+    pub category: TypeCategory,
+
+    /// The number of bits in a lane.
+    pub lane_bits: u32,
+
+    /// The number of lanes, which is 1 for scalars and the minimum lane count for dynamic
+    /// vectors.
+    pub lane_count: u32,
+
+    /// Whether this is a dynamic vector type.
+    pub dynamic: bool,
+}
+
+/// The kind of location a value of some [`Type`] is passed in, as reported by
+/// [`Type::abi_class`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AbiClass {
+    /// A general purpose integer register.
+    Integer,
+
+    /// A floating point or vector register.
+    FloatVector,
+
+    /// Memory, because the value doesn't fit in a register.
+    Memory,
+}
+
+/// A WebAssembly core value type, as returned by [`Type::to_wasm_valtype`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WasmValType {
+    /// `i32`.
+    I32,
+
+    /// `i64`.
+    I64,
+
+    /// `f32`.
+    F32,
+
+    /// `f64`.
+    F64,
+
+    /// `v128`.
+    V128,
+}
+
+/// How the integer lanes of a [`SignedType`] are interpreted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Signedness {
+    /// The lanes are signed two's complement integers.
+    Signed,
+
+    /// The lanes are unsigned integers.
+    Unsigned,
+
+    /// The lanes have no particular signedness, like a plain [`Type`].
+    Agnostic,
+}
+
+/// A [`Type`] together with the signedness of its integer lanes.
+///
+/// Cranelift types are sign-agnostic, and it's the instructions that decide how to interpret
+/// integer values. This can be used to carry a signedness through lowering alongside the type.
+/// It prints as `s32` or `u8x16` for signed and unsigned integer lanes, and like the plain type
+/// otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SignedType {
+    ty: Type,
+    signedness: Signedness,
+}
+
+impl SignedType {
+    /// Create a new `SignedType`.
+    ///
+    /// Only integer lanes have a signedness, so types with other lanes are always
+    /// [`Signedness::Agnostic`].
+    pub fn new(ty: Type, signedness: Signedness) -> Self {
+        let signedness = if ty.lane_type().is_int() {
+            signedness
+        } else {
+            Signedness::Agnostic
+        };
+        Self { ty, signedness }
+    }
+
+    /// Get the underlying sign-agnostic type.
+    pub fn ty(self) -> Type {
+        self.ty
+    }
+
+    /// Get the signedness of the integer lanes.
+    pub fn signedness(self) -> Signedness {
+        self.signedness
+    }
+}
+
+impl Display for SignedType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if !self.ty.lane_type().is_int() {
+            return Display::fmt(&self.ty, f);
+        }
+        let prefix = match self.signedness {
+            Signedness::Signed => 's',
+            Signedness::Unsigned => 'u',
+            Signedness::Agnostic => 'i',
+        };
+        write!(f, "{prefix}{}", self.ty.lane_bits())?;
+        if self.ty.is_vector() {
+            write!(f, "x{}", self.ty.lane_count())?;
+        } else if self.ty.is_dynamic_vector() {
+            write!(f, "x{}xN", self.ty.min_lane_count())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn basic_scalars() {
+        assert_eq!(INVALID, INVALID.lane_type());
+        assert_eq!(0, INVALID.bits());
+        assert_eq!(I8, I8.lane_type());
+        assert_eq!(I16, I16.lane_type());
+        assert_eq!(I32, I32.lane_type());
+        assert_eq!(I64, I64.lane_type());
+        assert_eq!(I128, I128.lane_type());
+        assert_eq!(F32, F32.lane_type());
+        assert_eq!(F16, F16.lane_type());
+        assert_eq!(F64, F64.lane_type());
+        assert_eq!(F128, F128.lane_type());
+        assert_eq!(I32, I32X4.lane_type());
+        assert_eq!(F64, F64X2.lane_type());
+
+        assert_eq!(INVALID.lane_bits(), 0);
+        assert_eq!(I8.lane_bits(), 8);
+        assert_eq!(I16.lane_bits(), 16);
+        assert_eq!(I32.lane_bits(), 32);
+        assert_eq!(I64.lane_bits(), 64);
+        assert_eq!(I128.lane_bits(), 128);
+        assert_eq!(F16.lane_bits(), 16);
+        assert_eq!(F32.lane_bits(), 32);
+        assert_eq!(F64.lane_bits(), 64);
+        assert_eq!(F128.lane_bits(), 128);
+    }
+
+    #[test]
+    fn float_types() {
+        for ty in [F16, F32, F64, F128] {
+            assert!(ty.is_float());
+            assert!(!ty.is_int());
+        }
+        assert!(!F16X8.is_float());
+        assert_eq!(F16.lane_bits(), 16);
+        assert_eq!(F16X8.lane_type(), F16);
+        assert_eq!(F16.as_truthy_pedantic(), I16);
+    }
+
+    #[test]
+    fn all_lane_types() {
+        assert!(Type::int_types().eq([I8, I16, I32, I64, I128]));
+        assert!(Type::int_types().all(|ty| ty.is_int()));
+        assert!(Type::float_types().eq([F16, F32, F64, F128]));
+        assert!(Type::float_types().all(|ty| ty.is_float()));
+        assert_eq!(Type::all_lane_types().count(), 9);
+        for ty in Type::all_lane_types() {
+            assert!(ty.is_lane());
+            assert_eq!(ty.lane_type(), ty);
+        }
+    }
+
+    #[test]
+    fn reduce_to_scalar() {
+        assert_eq!(I32X4.reduce_to_scalar(), I32);
+        assert_eq!(I32.reduce_to_scalar(), I32);
+        assert_eq!(F64X2.reduce_to_scalar(), F64);
+        assert_eq!(I8X16XN.reduce_to_scalar(), I8);
+    }
+
+    #[test]
+    fn reduction_accumulator() {
+        assert_eq!(I8X16.reduction_accumulator(), Some(I16));
+        assert_eq!(I16X8.reduction_accumulator(), Some(I32));
+        assert_eq!(I32X4.reduction_accumulator(), Some(I64));
+        assert_eq!(I64X2.reduction_accumulator(), Some(I128));
+        assert_eq!(I32.reduction_accumulator(), Some(I64));
+        assert_eq!(I8X16XN.reduction_accumulator(), Some(I16));
+        assert_eq!(I128.reduction_accumulator(), None);
+        assert_eq!(F32X4.reduction_accumulator(), None);
+        assert_eq!(INVALID.reduction_accumulator(), None);
+    }
+
+    #[test]
+    fn lane_type_at() {
+        assert_eq!(I32X4.lane_type_at(0), Some(I32));
+        assert_eq!(I32X4.lane_type_at(3), Some(I32));
+        assert_eq!(I32X4.lane_type_at(4), None);
+        assert_eq!(F64.lane_type_at(0), Some(F64));
+        assert_eq!(F64.lane_type_at(1), None);
+        assert_eq!(I32X4XN.lane_type_at(0), None);
+    }
+
+    #[test]
+    fn all_vector_types() {
+        let types: Vec<Type> = Type::all_vector_types(128).collect();
+        // Lane counts: i8: 1-16, i16/f16: 1-8, i32/f32: 1-4, i64/f64: 1-2, i128/f128: 1.
+        assert_eq!(types.len(), 5 + 2 * (4 + 3 + 2 + 1));
+        assert!(types.iter().all(|ty| ty.bits() <= 128));
+        assert!(types.contains(&I8X16));
+        assert!(types.contains(&F128));
+        assert!(!types.contains(&I32X8));
+        assert_eq!(types[..3], [I8, I8X2, I8X4]);
+
+        assert_eq!(Type::all_vector_types(4).count(), 0);
+    }
+
+    #[test]
+    fn typevar_functions() {
+        assert_eq!(INVALID.half_width(), None);
+        assert_eq!(INVALID.half_width(), None);
+        assert_eq!(I8.half_width(), None);
+        assert_eq!(I16.half_width(), Some(I8));
+        assert_eq!(I32.half_width(), Some(I16));
+        assert_eq!(I32X4.half_width(), Some(I16X4));
+        assert_eq!(I64.half_width(), Some(I32));
+        assert_eq!(I128.half_width(), Some(I64));
+        assert_eq!(F16.half_width(), None);
+        assert_eq!(F32.half_width(), Some(F16));
+        assert_eq!(F64.half_width(), Some(F32));
+        assert_eq!(F128.half_width(), Some(F64));
+
+        assert_eq!(INVALID.double_width(), None);
+        assert_eq!(I8.double_width(), Some(I16));
+        assert_eq!(I16.double_width(), Some(I32));
+        assert_eq!(I32.double_width(), Some(I64));
+        assert_eq!(I32X4.double_width(), Some(I64X4));
+        assert_eq!(I64.double_width(), Some(I128));
+        assert_eq!(I128.double_width(), None);
+        assert_eq!(F16.double_width(), Some(F32));
+        assert_eq!(F32.double_width(), Some(F64));
+        assert_eq!(F64.double_width(), Some(F128));
+        assert_eq!(F128.double_width(), None);
+    }
+
+    #[test]
+    fn compare_result() {
+        assert_eq!(I32.compare_result(), Some(I8));
+        assert_eq!(F64.compare_result(), Some(I8));
+        assert_eq!(I8.compare_result(), Some(I8));
+        assert_eq!(F64X2.compare_result(), Some(I64X2));
+        assert_eq!(I16X8.compare_result(), Some(I16X8));
+        assert_eq!(F32X4XN.compare_result(), Some(I32X4XN));
+        assert_eq!(INVALID.compare_result(), None);
+        assert_eq!(Type(0x7d).compare_result(), None);
+    }
+
+    #[test]
+    fn shuffle_mask_type() {
+        assert_eq!(I32X4.shuffle_mask_type(), Some(I8X4));
+        assert_eq!(I8X16.shuffle_mask_type(), Some(I8X16));
+        assert_eq!(F64X2.shuffle_mask_type(), Some(I8X2));
+        assert_eq!(I16X32.shuffle_mask_type(), Some(I8X32));
+        assert_eq!(I8.by(256).unwrap().shuffle_mask_type(), I8.by(256));
+        assert_eq!(I32.shuffle_mask_type(), None);
+        assert_eq!(I32X4XN.shuffle_mask_type(), None);
+        assert_eq!(INVALID.shuffle_mask_type(), None);
+    }
+
+    #[test]
+    fn bitcast_to_int() {
+        assert_eq!(F32.bitcast_to_int(), Some(I32));
+        assert_eq!(F64.bitcast_to_int(), Some(I64));
+        assert_eq!(F16.bitcast_to_int(), Some(I16));
+        assert_eq!(F128.bitcast_to_int(), Some(I128));
+        assert_eq!(I32.bitcast_to_int(), Some(I32));
+        assert_eq!(F32X4.bitcast_to_int(), None);
+        assert_eq!(I32X4XN.bitcast_to_int(), None);
+        assert_eq!(INVALID.bitcast_to_int(), None);
+        assert_eq!(Type(0x7d).bitcast_to_int(), None);
+    }
+
+    #[test]
+    fn bit_mask_type() {
+        assert_eq!(F32X4.bit_mask_type(), Some(I32X4));
+        assert_eq!(F64.bit_mask_type(), Some(I64));
+        assert_eq!(F16X8XN.bit_mask_type(), Some(I16X8XN));
+        assert_eq!(I8X16.bit_mask_type(), Some(I8X16));
+        assert_eq!(INVALID.bit_mask_type(), None);
+        assert_eq!(Type(0x7d).bit_mask_type(), None);
+    }
+
+    #[test]
+    fn max_shift_amount() {
+        assert_eq!(I8.max_shift_amount(), Some(7));
+        assert_eq!(I32.max_shift_amount(), Some(31));
+        assert_eq!(I64.max_shift_amount(), Some(63));
+        assert_eq!(I128.max_shift_amount(), Some(127));
+        assert_eq!(I16X8.max_shift_amount(), Some(15));
+        assert_eq!(F32.max_shift_amount(), None);
+        assert_eq!(F64X2.max_shift_amount(), None);
+        assert_eq!(INVALID.max_shift_amount(), None);
+    }
+
+    #[test]
+    fn int_widths() {
+        assert_eq!(I8.wider_int(), Some(I16));
+        assert_eq!(I64.wider_int(), Some(I128));
+        assert_eq!(I128.wider_int(), None);
+        assert_eq!(I8X4.wider_int(), Some(I16X4));
+        assert_eq!(F32.wider_int(), None);
+        assert_eq!(INVALID.wider_int(), None);
+
+        assert_eq!(I16.narrower_int(), Some(I8));
+        assert_eq!(I128.narrower_int(), Some(I64));
+        assert_eq!(I8.narrower_int(), None);
+        assert_eq!(I32X4.narrower_int(), Some(I16X4));
+        assert_eq!(F64.narrower_int(), None);
+    }
+
+    #[test]
+    fn float_widths() {
+        assert_eq!(F16.wider_float(), Some(F32));
+        assert_eq!(F32.wider_float(), Some(F64));
+        assert_eq!(F64.wider_float(), Some(F128));
+        assert_eq!(F128.wider_float(), None);
+        assert_eq!(F32X4.wider_float(), Some(F64X4));
+        assert_eq!(I32.wider_float(), None);
+
+        assert_eq!(F64.narrower_float(), Some(F32));
+        assert_eq!(F32.narrower_float(), Some(F16));
+        assert_eq!(F16.narrower_float(), None);
+        assert_eq!(F32X4.narrower_float(), Some(F16X4));
+        assert_eq!(I64.narrower_float(), None);
+
+        // Unlike `split_lanes` and `merge_lanes`, the lane count is preserved.
+        assert_eq!(F64X2.narrower_float(), Some(F32X2));
+        assert_eq!(F32X4.wider_float(), Some(F64X4));
+        assert_eq!(F128X2.wider_float(), None);
+        assert_eq!(F16X8.narrower_float(), None);
+    }
+
+    #[test]
+    fn widening_mul_result() {
+        assert_eq!(I16X8.widening_mul_result(), Some(I32X4));
+        assert_eq!(I8X16.widening_mul_result(), Some(I16X8));
+        assert_eq!(I32X4.widening_mul_result(), Some(I64X2));
+        assert_eq!(I64X2.widening_mul_result(), Some(I128));
+        assert_eq!(I64.widening_mul_result(), None);
+        assert_eq!(I32.widening_mul_result(), None);
+        assert_eq!(I128X2.widening_mul_result(), None);
+        assert_eq!(F32X4.widening_mul_result(), None);
+        assert_eq!(I16X8XN.widening_mul_result(), None);
+    }
+
+    #[test]
+    fn split_concat_pair() {
+        assert_eq!(I64.split_pair(), Some((I32, I32)));
+        assert_eq!(I16.split_pair(), Some((I8, I8)));
+        assert_eq!(I128.split_pair(), Some((I64, I64)));
+        assert_eq!(I8.split_pair(), None);
+        assert_eq!(F64.split_pair(), None);
+        assert_eq!(I64X2.split_pair(), None);
+        assert_eq!(I32.concat_pair(), Some(I64));
+        assert_eq!(I128.concat_pair(), None);
+        assert_eq!(F32.concat_pair(), None);
+        assert_eq!(I32X4.concat_pair(), None);
+        for ty in [I16, I32, I64, I128] {
+            let (lo, hi) = ty.split_pair().unwrap();
+            assert_eq!(lo, hi);
+            assert_eq!(lo.concat_pair(), Some(ty));
+        }
+    }
+
+    #[test]
+    fn reshape_to_lane_bits() {
+        assert_eq!(I8X16.reshape_to_lane_bits(32), Some(I32X4));
+        assert_eq!(I32X4.reshape_to_lane_bits(8), Some(I8X16));
+        assert_eq!(F64X2.reshape_to_lane_bits(16), Some(I16X8));
+        assert_eq!(I64.reshape_to_lane_bits(32), Some(I32X2));
+        assert_eq!(I32X4.reshape_to_lane_bits(128), Some(I128));
+        assert_eq!(I64X2.reshape_to_lane_bits(64), Some(I64X2));
+        assert_eq!(I32X4.reshape_to_lane_bits(256), None);
+        assert_eq!(I16.reshape_to_lane_bits(32), None);
+        assert_eq!(I32X4.reshape_to_lane_bits(24), None);
+        assert_eq!(I32X4XN.reshape_to_lane_bits(8), None);
+        assert_eq!(INVALID.reshape_to_lane_bits(8), None);
+    }
+
+    #[test]
+    fn split_merge_lanes() {
+        assert_eq!(I16X4.split_lanes(), Some(I8X8));
+        assert_eq!(I32X2.split_lanes(), Some(I16X4));
+        assert_eq!(I64.split_lanes(), Some(I32X2));
+        assert_eq!(F64X2.split_lanes(), Some(F32X4));
+        assert_eq!(I8X16.split_lanes(), None);
+        assert_eq!(I8.split_lanes(), None);
+
+        assert_eq!(I8X8.merge_lanes(), Some(I16X4));
+        assert_eq!(I16X4.merge_lanes(), Some(I32X2));
+        assert_eq!(I32X2.merge_lanes(), Some(I64));
+        assert_eq!(I8.merge_lanes(), None);
+        assert_eq!(I128X2.merge_lanes(), None);
+
+        for ty in [I16X4, I32X2, I64, F32X4] {
+            assert_eq!(ty.split_lanes().unwrap().merge_lanes(), Some(ty));
+        }
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Type::default(), INVALID);
+
+        #[derive(Default)]
+        struct Slot {
+            ty: Type,
+        }
+        assert!(Slot::default().ty.is_invalid());
+    }
+
+    #[test]
+    fn checked_half_vector() {
+        assert_eq!(I32X4.checked_half_vector(), Ok(I32X2));
+        assert_eq!(I32.by(4).unwrap().checked_half_vector(), Ok(I32X2));
+        assert_eq!(I32X2.checked_half_vector(), Ok(I32));
+        assert_eq!(I32.checked_half_vector(), Err(TypeError::NotAVector));
+        assert_eq!(INVALID.checked_half_vector(), Err(TypeError::NotAVector));
+        assert_eq!(I32X4XN.checked_half_vector(), Err(TypeError::DynamicVector));
+        assert_eq!(TypeError::NotAVector.to_string(), "not a SIMD vector type");
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(!I32.is_vector());
+        assert!(I32.by(2).unwrap().is_vector());
+        assert!(F64X2.is_vector());
+        assert!(!INVALID.is_vector());
+        assert!(!I32X4XN.is_vector());
+
+        assert!(I32.is_lane());
+        assert!(!I32X4.is_lane());
+        assert!(INVALID.is_special());
+        assert!(INVALID.is_invalid());
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const VECTOR: Option<Type> = I32.by(4);
+        const BITS: u32 = F64X2.bits();
+        const LANE: Type = I16X8.lane_type();
+        const IS_INT: bool = I64.is_int();
+        assert_eq!(VECTOR, Some(I32X4));
+        assert_eq!(BITS, 128);
+        assert_eq!(LANE, I16);
+        assert!(IS_INT);
+    }
+
+    #[test]
+    fn bit_compatible() {
+        assert!(I32.bit_compatible(F32));
+        assert!(I64.bit_compatible(F32X2));
+        assert!(I8X16.bit_compatible(I128));
+        assert!(F64X2.bit_compatible(I32X4));
+        assert!(I16.bit_compatible(I16));
+
+        assert!(!I32.bit_compatible(I64));
+        assert!(!F32X4.bit_compatible(F32X2));
+        assert!(!INVALID.bit_compatible(INVALID));
+        assert!(!I32X4XN.bit_compatible(I32X4XN));
+    }
+
+    #[test]
+    fn same_lane_type() {
+        assert!(I32.same_lane_type(I32X8));
+        assert!(I32X4.same_lane_type(I32X16));
+        assert!(I32X4XN.same_lane_type(I32));
+        assert!(F64.same_lane_type(F64));
+        assert!(!I32.same_lane_type(I16X8));
+        assert!(!I32X4.same_lane_type(F32X4));
+        assert!(!I64.same_lane_type(F64X2));
+    }
+
+    #[test]
+    fn lane_count_shape() {
+        assert!(I32X4.same_lane_count(F32X4));
+        assert!(I8X4.same_lane_count(I64X4));
+        assert!(I32.same_lane_count(F64));
+        assert!(!I32X4.same_lane_count(I32X8));
+        assert!(!I32X4.same_lane_count(I32X4XN));
+        assert!(I32X4XN.same_lane_count(F32X4XN));
+
+        assert_eq!(I8.with_lane_count_of(I32X4), Some(I8X4));
+        assert_eq!(F64X2.with_lane_count_of(I16X8), Some(F64X8));
+        assert_eq!(I32X4.with_lane_count_of(I64), Some(I32));
+        assert_eq!(I32.with_lane_count_of(I32X4XN), None);
+        assert_eq!(I32X4XN.with_lane_count_of(I32X4), None);
+        assert_eq!(INVALID.with_lane_count_of(I32X4), None);
+    }
+
+    #[test]
+    fn common() {
+        assert_eq!(I8.common(I32), Some(I32));
+        assert_eq!(I64.common(I16), Some(I64));
+        assert_eq!(F32.common(F64), Some(F64));
+        assert_eq!(I8X4.common(I32X4), Some(I32X4));
+        assert_eq!(I8.common(I8), Some(I8));
+
+        assert_eq!(I32.common(F32), None);
+        assert_eq!(F64.common(I8), None);
+        assert_eq!(I32.common(I32X2), None);
+        assert_eq!(I32X4.common(I32X4XN), None);
+        assert_eq!(INVALID.common(INVALID), None);
+    }
+
+    #[test]
+    fn vector_categories() {
+        assert!(F32X4.is_float_vector());
+        assert!(!F32X4.is_int_vector());
+        assert!(I8X16.is_int_vector());
+        assert!(!I8X16.is_float_vector());
+        for ty in [F32, I32, INVALID, I32X4XN, F64X2XN] {
+            assert!(!ty.is_int_vector());
+            assert!(!ty.is_float_vector());
+        }
+    }
+
+    #[test]
+    fn simd_lane_legal() {
+        assert!(!INVALID.is_simd_lane_legal());
+        assert!(Type::all_lane_types().all(|ty| ty.is_simd_lane_legal()));
+        assert!(!I32X4.is_simd_lane_legal());
+        assert!(!I32X4XN.is_simd_lane_legal());
+    }
+
+    #[test]
+    fn lane_of_vector() {
+        assert!(I32.is_lane_of(I32X4));
+        assert!(F64.is_lane_of(F64X2XN));
+        assert!(!I16.is_lane_of(I32X4));
+        assert!(!F32.is_lane_of(I32X4));
+        assert!(I64.is_lane_of(I64));
+        assert!(!I64.is_lane_of(I32));
+        assert!(!I32X4.is_lane_of(I32X4));
+        assert!(!INVALID.is_lane_of(INVALID));
+    }
+
+    #[test]
+    fn with_lane_type() {
+        assert_eq!(I32X4.with_lane_type(F32), Some(F32X4));
+        assert_eq!(F64X2.with_lane_type(I64), Some(I64X2));
+        assert_eq!(I8X16.with_lane_type(I32), Some(I32X16));
+        assert_eq!(I16X8XN.with_lane_type(F16), Some(F16X8XN));
+        assert_eq!(I32.with_lane_type(F64), Some(F64));
+        assert_eq!(
+            I32X4.with_lane_type(F32).unwrap().log2_lane_count(),
+            I32X4.log2_lane_count()
+        );
+        assert_eq!(I32X4.with_lane_type(F32X4), None);
+        assert_eq!(I32X4.with_lane_type(INVALID), None);
+        assert_eq!(INVALID.with_lane_type(I32), None);
+
+        // Lane replacement keeps dynamic vectors dynamic.
+        assert_eq!(F32X4XN.as_int(), I32X4XN);
+        assert_eq!(F64X2XN.as_truthy_pedantic(), I64X2XN);
+    }
+
+    #[test]
+    #[should_panic(expected = "vector_to_dynamic requires a fixed vector type, got types::I32")]
+    fn vector_to_dynamic_scalar() {
+        I32.vector_to_dynamic();
+    }
+
+    #[test]
+    #[should_panic(expected = "vector_to_dynamic requires a fixed vector type, got types::I32X4XN")]
+    fn vector_to_dynamic_dynamic() {
+        I32X4XN.vector_to_dynamic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "dynamic_to_vector requires a dynamic vector type, got types::INVALID"
+    )]
+    fn dynamic_to_vector_invalid() {
+        INVALID.dynamic_to_vector();
+    }
+
+    #[test]
+    #[should_panic(expected = "dynamic_to_vector requires a dynamic vector type, got Type(0x7d)")]
+    fn dynamic_to_vector_malformed() {
+        Type(0x7d).dynamic_to_vector();
+    }
+
+    #[test]
+    fn encode_decode() {
+        let types: Vec<Type> = Type::all_lane_types()
+            .chain([
+                INVALID, I8X16, I32X4, F64X2, I8X64, I32X4XN, I8X64XN, F64X2XN,
+            ])
+            .collect();
+        let mut buf = Vec::new();
+        for ty in &types {
+            ty.encode(&mut buf);
+        }
+        let mut input = &buf[..];
+        for &ty in &types {
+            let (decoded, len) = Type::decode(input).unwrap();
+            assert_eq!(decoded, ty);
+            input = &input[len..];
+        }
+        assert!(input.is_empty());
+
+        let mut buf = Vec::new();
+        INVALID.encode(&mut buf);
+        assert_eq!(buf, [0x00]);
+        buf.clear();
+        I32.encode(&mut buf);
+        assert_eq!(buf, [0x76]);
+        buf.clear();
+        I32X4.encode(&mut buf);
+        assert_eq!(buf, [0x96, 0x01]);
+
+        assert_eq!(Type::decode(&[]), Err(TypeError::InvalidEncoding));
+        assert_eq!(Type::decode(&[0x96]), Err(TypeError::InvalidEncoding));
+        assert_eq!(
+            Type::decode(&[0xff, 0xff, 0xff, 0x01]),
+            Err(TypeError::InvalidEncoding)
+        );
+        assert_eq!(
+            Type::decode(&[0xff, 0xff, 0x7f]),
+            Err(TypeError::InvalidEncoding)
+        );
+        assert_eq!(Type::decode(&[0x7d]), Err(TypeError::InvalidRepr(0x7d)));
+        assert_eq!(Type::decode(&[0xf6, 0x00]), Err(TypeError::InvalidEncoding));
+        assert_eq!(
+            Type::decode(&[0x96, 0x81, 0x00]),
+            Err(TypeError::InvalidEncoding)
+        );
+        assert_eq!(Type::decode(&[0x00, 0x00]), Ok((INVALID, 1)));
+    }
+
+    #[test]
+    fn matches_pattern() {
+        assert!(I32.matches_pattern("i*"));
+        assert!(I8X16.matches_pattern("i*"));
+        assert!(!F32.matches_pattern("i*"));
+        assert!(F32X4.matches_pattern("*x4"));
+        assert!(I32X4.matches_pattern("*x4"));
+        assert!(!I32X8.matches_pattern("*x4"));
+        assert!(!I32.matches_pattern("*x4"));
+        assert!(I64X2.matches_pattern("*64*"));
+        assert!(F64.matches_pattern("f64"));
+        assert!(!F64X2.matches_pattern("f64"));
+        assert!(I128.matches_pattern("*"));
+        assert!(!INVALID.matches_pattern("*"));
+        assert!(!Type(0x7d).matches_pattern("*"));
+    }
+
+    #[test]
+    fn saturating_by() {
+        assert_eq!(I32.saturating_by(4), Some(I32X4));
+        assert_eq!(I8.saturating_by(256), Some(I8.by(256).unwrap()));
+        assert_eq!(I8.saturating_by(1024), I8.by(256));
+        assert_eq!(I8X16.saturating_by(1 << 20), I8.by(256));
+        assert_eq!(F64X2.saturating_by(512).unwrap().lane_type(), F64);
+        assert_eq!(F64X2.saturating_by(512).unwrap().lane_count(), 256);
+        assert_eq!(I32.saturating_by(3), None);
+        assert_eq!(INVALID.saturating_by(2), None);
+        assert_eq!(I32X4XN.saturating_by(2), None);
+    }
+
+    #[test]
+    fn abi_class() {
+        assert_eq!(I8.abi_class(), Some(AbiClass::Integer));
+        assert_eq!(I64.abi_class(), Some(AbiClass::Integer));
+        assert_eq!(I128.abi_class(), Some(AbiClass::Memory));
+        assert_eq!(F64.abi_class(), Some(AbiClass::FloatVector));
+        assert_eq!(F128.abi_class(), Some(AbiClass::FloatVector));
+        assert_eq!(I32X4.abi_class(), Some(AbiClass::FloatVector));
+        assert_eq!(I32X4XN.abi_class(), Some(AbiClass::FloatVector));
+        assert_eq!(I32X8.abi_class(), Some(AbiClass::Memory));
+        assert_eq!(
+            I32X8.abi_class_with_threshold(256),
+            Some(AbiClass::FloatVector)
+        );
+        assert_eq!(F64X2.abi_class_with_threshold(64), Some(AbiClass::Memory));
+        assert_eq!(INVALID.abi_class(), None);
+    }
+
+    #[test]
+    fn dwarf_base_type() {
+        assert_eq!(I8.dwarf_base_type(), Some((5, 1)));
+        assert_eq!(I32.dwarf_base_type(), Some((5, 4)));
+        assert_eq!(I128.dwarf_base_type(), Some((5, 16)));
+        assert_eq!(F16.dwarf_base_type(), Some((4, 2)));
+        assert_eq!(F64.dwarf_base_type(), Some((4, 8)));
+        assert_eq!(I32X4.dwarf_base_type(), None);
+        assert_eq!(F32X4XN.dwarf_base_type(), None);
+        assert_eq!(INVALID.dwarf_base_type(), None);
+    }
+
+    #[test]
+    fn llvm_name() {
+        assert_eq!(I8.llvm_name().as_deref(), Some("i8"));
+        assert_eq!(I32.llvm_name().as_deref(), Some("i32"));
+        assert_eq!(I128.llvm_name().as_deref(), Some("i128"));
+        assert_eq!(F16.llvm_name().as_deref(), Some("half"));
+        assert_eq!(F32.llvm_name().as_deref(), Some("float"));
+        assert_eq!(F64.llvm_name().as_deref(), Some("double"));
+        assert_eq!(F128.llvm_name().as_deref(), Some("fp128"));
+        assert_eq!(I32X4.llvm_name().as_deref(), Some("<4 x i32>"));
+        assert_eq!(F64X2.llvm_name().as_deref(), Some("<2 x double>"));
+        assert_eq!(I32X4XN.llvm_name().as_deref(), Some("<vscale x 4 x i32>"));
+        assert_eq!(INVALID.llvm_name().as_deref(), Some("void"));
+        assert_eq!(Type(0x7d).llvm_name(), None);
+        assert_eq!(Type(0x8d).llvm_name(), None);
+    }
+
+    #[test]
+    fn to_wasm_valtype() {
+        assert_eq!(I8.to_wasm_valtype(), Some(WasmValType::I32));
+        assert_eq!(I16.to_wasm_valtype(), Some(WasmValType::I32));
+        assert_eq!(I32.to_wasm_valtype(), Some(WasmValType::I32));
+        assert_eq!(I64.to_wasm_valtype(), Some(WasmValType::I64));
+        assert_eq!(F32.to_wasm_valtype(), Some(WasmValType::F32));
+        assert_eq!(F64.to_wasm_valtype(), Some(WasmValType::F64));
+        for ty in [I8X16, I16X8, I32X4, I64X2, F16X8, F32X4, F64X2] {
+            assert_eq!(ty.to_wasm_valtype(), Some(WasmValType::V128));
+        }
+        assert_eq!(I128.to_wasm_valtype(), None);
+        assert_eq!(F16.to_wasm_valtype(), None);
+        assert_eq!(F128.to_wasm_valtype(), None);
+        assert_eq!(I32X8.to_wasm_valtype(), None);
+        assert_eq!(I32X2.to_wasm_valtype(), None);
+        assert_eq!(I32X4XN.to_wasm_valtype(), None);
+        assert_eq!(INVALID.to_wasm_valtype(), None);
+    }
+
+    #[test]
+    fn from_wasm_valtype() {
+        assert_eq!(Type::from_wasm_valtype(WasmValType::I32), I32);
+        assert_eq!(Type::from_wasm_valtype(WasmValType::I64), I64);
+        assert_eq!(Type::from_wasm_valtype(WasmValType::F32), F32);
+        assert_eq!(Type::from_wasm_valtype(WasmValType::F64), F64);
+        assert_eq!(Type::from_wasm_valtype(WasmValType::V128), I8X16);
+        assert_eq!(Type::from_wasm_valtype(WasmValType::V128).bits(), 128);
+        for w in [
+            WasmValType::I32,
+            WasmValType::I64,
+            WasmValType::F32,
+            WasmValType::F64,
+            WasmValType::V128,
+        ] {
+            assert_eq!(Type::from_wasm_valtype(w).to_wasm_valtype(), Some(w));
+        }
+    }
+
+    #[test]
+    fn binop_result() {
+        assert_eq!(I32.binop_result(I32), Ok(I32));
+        assert_eq!(F64X2.binop_result(F64X2), Ok(F64X2));
+        assert_eq!(
+            I32.binop_result(I64),
+            Err(TypeError::Mismatch { lhs: I32, rhs: I64 })
+        );
+        assert_eq!(
+            I32X4.binop_result(F32X4),
+            Err(TypeError::Mismatch {
+                lhs: I32X4,
+                rhs: F32X4
+            })
+        );
+        assert_eq!(
+            INVALID.binop_result(INVALID),
+            Err(TypeError::Mismatch {
+                lhs: INVALID,
+                rhs: INVALID
+            })
+        );
+        assert_eq!(
+            I32.binop_result(INVALID).unwrap_err().to_string(),
+            "operand types types::I32 and types::INVALID don't match"
+        );
+    }
+
+    #[test]
+    fn describe() {
+        let info = |category, lane_bits, lane_count, dynamic| TypeInfo {
+            category,
+            lane_bits,
+            lane_count,
+            dynamic,
+        };
+        assert_eq!(I32.describe(), info(TypeCategory::Int, 32, 1, false));
+        assert_eq!(F64X2.describe(), info(TypeCategory::Float, 64, 2, false));
+        assert_eq!(I8X16XN.describe(), info(TypeCategory::Int, 8, 16, true));
+        assert_eq!(INVALID.describe(), info(TypeCategory::Invalid, 0, 1, false));
+    }
+
+    #[test]
+    fn vectors() {
+        let big = F64.by(256).unwrap();
+        assert_eq!(big.lane_bits(), 64);
+        assert_eq!(big.lane_count(), 256);
+        assert_eq!(big.bits(), 64 * 256);
+
+        // Check that the generated constants match the computed vector types.
+        assert_eq!(I32.by(4), Some(I32X4));
+        assert_eq!(F64.by(8), Some(F64X8));
+    }
+
+    #[test]
+    fn half_vector() {
+        assert_eq!(I32.half_vector(), None);
+        assert_eq!(I32X4.half_vector(), Some(I32X2));
+        assert_eq!(I32X2.half_vector(), Some(I32));
+        assert_eq!(I32X4XN.half_vector(), None);
+    }
+
+    #[test]
+    fn double_vector() {
+        assert_eq!(I32.double_vector(), Some(I32X2));
+        assert_eq!(I32X4.double_vector(), Some(I32X8));
+        assert_eq!(I8.by(256).unwrap().double_vector(), None);
+        for ty in [I8, I32, F64X2, I16X16] {
+            assert_eq!(ty.double_vector().unwrap().half_vector(), Some(ty));
+        }
+    }
+
+    #[test]
+    fn checked_by() {
+        assert_eq!(INVALID.checked_by(2), Err(TypeError::InvalidLane));
+        assert_eq!(I32.checked_by(3), Err(TypeError::NonPowerOfTwoLanes));
+        assert_eq!(I32.checked_by(0), Err(TypeError::NonPowerOfTwoLanes));
+        assert_eq!(I8.checked_by(512), Err(TypeError::TooManyLanes));
+        assert_eq!(I8X16.checked_by(32), Err(TypeError::TooManyLanes));
+        assert_eq!(I32X4XN.checked_by(2), Err(TypeError::DynamicVector));
+        assert_eq!(I32X2.checked_by(2), Ok(I32X4));
     }
 
-    /// Converts from a bit-level representation of the type back to a
-    /// `Type`.
-    pub(crate) fn from_repr(bits: u16) -> Type {
-        Type(bits)
+    #[test]
+    fn all_lanes() {
+        let lane_counts: Vec<u32> = I32.all_lanes().map(|ty| ty.lane_count()).collect();
+        assert_eq!(lane_counts, [1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert!(I32.all_lanes().all(|ty| ty.lane_type() == I32));
+        assert_eq!(I8X64.all_lanes().next(), Some(I8X64));
+        assert_eq!(I8X64.all_lanes().count(), 3);
+        assert_eq!(I32X4XN.all_lanes().count(), 1);
     }
-}
 
-impl Display for Type {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        if self.is_int() {
-            write!(f, "i{}", self.lane_bits())
-        } else if self.is_float() {
-            write!(f, "f{}", self.lane_bits())
-        } else if self.is_vector() {
-            write!(f, "{}x{}", self.lane_type(), self.lane_count())
-        } else if self.is_dynamic_vector() {
-            write!(f, "{:?}x{}xN", self.lane_type(), self.min_lane_count())
-        } else {
-            match *self {
-                INVALID => panic!("INVALID encountered"),
-                _ => panic!("Unknown Type(0x{:x})", self.0),
-            }
-        }
+    #[test]
+    fn bits_no_overflow() {
+        // The widest expressible types must not overflow the bit count.
+        assert_eq!(I128.by(256).unwrap().bits(), 128 * 256);
+        assert_eq!(F128.by(256).unwrap().bits(), 128 * 256);
+        assert_eq!(F128.by(256).unwrap().bytes(), 16 * 256);
     }
-}
 
-impl Debug for Type {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        if self.is_int() {
-            write!(f, "types::I{}", self.lane_bits())
-        } else if self.is_float() {
-            write!(f, "types::F{}", self.lane_bits())
-        } else if self.is_vector() {
-            write!(f, "{:?}X{}", self.lane_type(), self.lane_count())
-        } else if self.is_dynamic_vector() {
-            write!(f, "{:?}X{}XN", self.lane_type(), self.min_lane_count())
-        } else {
-            match *self {
-                INVALID => write!(f, "types::INVALID"),
-                _ => write!(f, "Type(0x{:x})", self.0),
-            }
+    #[test]
+    fn vector_of() {
+        assert_eq!(Type::vector_of(I32, 4), Ok(I32X4));
+        assert_eq!(Type::vector_of(F64, 1), Ok(F64));
+        assert_eq!(Type::vector_of(I32X4, 2), Err(TypeError::InvalidLane));
+        assert_eq!(Type::vector_of(INVALID, 2), Err(TypeError::InvalidLane));
+        assert_eq!(Type::vector_of(I8, 6), Err(TypeError::NonPowerOfTwoLanes));
+        assert_eq!(Type::vector_of(I8, 512), Err(TypeError::TooManyLanes));
+    }
+
+    #[test]
+    fn bits_rounded_up_pow2() {
+        assert_eq!(I8.bits_rounded_up_pow2(), 8);
+        assert_eq!(I32.bits_rounded_up_pow2(), 32);
+        assert_eq!(F16X8.bits_rounded_up_pow2(), 128);
+        assert_eq!(INVALID.bits_rounded_up_pow2(), 0);
+        assert_eq!(I32X4XN.bits_rounded_up_pow2(), 0);
+        for ty in Type::all_vector_types(2048) {
+            assert_eq!(ty.bits_rounded_up_pow2(), ty.bits());
         }
     }
-}
 
-impl Default for Type {
-    fn default() -> Self {
-        INVALID
+    #[test]
+    fn checked_bits() {
+        assert_eq!(I8.checked_bits(), Some(8));
+        assert_eq!(I32X4.checked_bits(), Some(128));
+        assert_eq!(F128.checked_bits(), Some(128));
+        assert_eq!(INVALID.checked_bits(), Some(0));
+        assert_eq!(I32X4XN.checked_bits(), Some(0));
+        assert_eq!(Type(0x7d).bits(), 0);
+        assert_eq!(Type(0x7d).checked_bits(), None);
+        assert_eq!(Type(0x04).checked_bits(), None);
+        assert_eq!(Type(0x194).checked_bits(), None);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloc::string::ToString;
+    #[test]
+    fn bytes() {
+        assert_eq!(INVALID.bytes(), 0);
+        assert_eq!(I8.bytes(), 1);
+        assert_eq!(I32.bytes(), 4);
+        assert_eq!(F64.bytes(), 8);
+        assert_eq!(I128.bytes(), 16);
+        assert_eq!(I8X16.bytes(), 16);
+        assert_eq!(F16X4.bytes(), 8);
+    }
 
     #[test]
-    fn basic_scalars() {
-        assert_eq!(INVALID, INVALID.lane_type());
-        assert_eq!(0, INVALID.bits());
-        assert_eq!(I8, I8.lane_type());
-        assert_eq!(I16, I16.lane_type());
-        assert_eq!(I32, I32.lane_type());
-        assert_eq!(I64, I64.lane_type());
-        assert_eq!(I128, I128.lane_type());
-        assert_eq!(F32, F32.lane_type());
-        assert_eq!(F16, F16.lane_type());
-        assert_eq!(F64, F64.lane_type());
-        assert_eq!(F128, F128.lane_type());
-        assert_eq!(I32, I32X4.lane_type());
-        assert_eq!(F64, F64X2.lane_type());
+    fn index_type() {
+        assert_eq!(I32.index_type(), I8);
+        assert_eq!(I32X4.index_type(), I8);
+        assert_eq!(I8.by(256).unwrap().index_type(), I8);
+        assert_eq!(I8X16XN.index_type(), I8);
+    }
 
-        assert_eq!(INVALID.lane_bits(), 0);
-        assert_eq!(I8.lane_bits(), 8);
-        assert_eq!(I16.lane_bits(), 16);
-        assert_eq!(I32.lane_bits(), 32);
-        assert_eq!(I64.lane_bits(), 64);
-        assert_eq!(I128.lane_bits(), 128);
-        assert_eq!(F16.lane_bits(), 16);
-        assert_eq!(F32.lane_bits(), 32);
-        assert_eq!(F64.lane_bits(), 64);
-        assert_eq!(F128.lane_bits(), 128);
+    #[test]
+    fn register_count() {
+        assert_eq!(I32X16.register_count(128), Some(4));
+        assert_eq!(I32X4.register_count(128), Some(1));
+        assert_eq!(I32X2.register_count(128), Some(1));
+        assert_eq!(I64.register_count(32), Some(2));
+        assert_eq!(I128.register_count(64), Some(2));
+        assert_eq!(I8.register_count(64), Some(1));
+        assert_eq!(INVALID.register_count(64), Some(0));
+        assert_eq!(I32X4XN.register_count(128), Some(0));
+        assert_eq!(I32.register_count(0), None);
     }
 
     #[test]
-    fn typevar_functions() {
-        assert_eq!(INVALID.half_width(), None);
-        assert_eq!(INVALID.half_width(), None);
-        assert_eq!(I8.half_width(), None);
-        assert_eq!(I16.half_width(), Some(I8));
-        assert_eq!(I32.half_width(), Some(I16));
-        assert_eq!(I32X4.half_width(), Some(I16X4));
-        assert_eq!(I64.half_width(), Some(I32));
-        assert_eq!(I128.half_width(), Some(I64));
-        assert_eq!(F16.half_width(), None);
-        assert_eq!(F32.half_width(), Some(F16));
-        assert_eq!(F64.half_width(), Some(F32));
-        assert_eq!(F128.half_width(), Some(F64));
+    fn register_subtype() {
+        assert_eq!(I32X16.register_subtype(128), Some(I32X4));
+        assert_eq!(I8X64.register_subtype(256), Some(I8X32));
+        assert_eq!(I128X2.register_subtype(128), Some(I128));
+        assert_eq!(
+            I32X16.register_subtype(128).unwrap().bits() * I32X16.register_count(128).unwrap(),
+            I32X16.bits()
+        );
+        assert_eq!(I32X4.register_subtype(128), None);
+        assert_eq!(I32X2.register_subtype(128), None);
+        assert_eq!(I64X4.register_subtype(32), None);
+        assert_eq!(I32X8.register_subtype(96), None);
+        assert_eq!(I64.register_subtype(32), None);
+        assert_eq!(I32X4XN.register_subtype(64), None);
+    }
 
-        assert_eq!(INVALID.double_width(), None);
-        assert_eq!(I8.double_width(), Some(I16));
-        assert_eq!(I16.double_width(), Some(I32));
-        assert_eq!(I32.double_width(), Some(I64));
-        assert_eq!(I32X4.double_width(), Some(I64X4));
-        assert_eq!(I64.double_width(), Some(I128));
-        assert_eq!(I128.double_width(), None);
-        assert_eq!(F16.double_width(), Some(F32));
-        assert_eq!(F32.double_width(), Some(F64));
-        assert_eq!(F64.double_width(), Some(F128));
-        assert_eq!(F128.double_width(), None);
+    #[test]
+    fn widen_to_register() {
+        assert_eq!(I8.widen_to_register(64), Some(I32));
+        assert_eq!(I16.widen_to_register(32), Some(I32));
+        assert_eq!(I64.widen_to_register(64), Some(I64));
+        assert_eq!(F64.widen_to_register(64), None);
+        assert_eq!(F16.widen_to_register(64), None);
+        assert_eq!(I8.widen_to_register(16), Some(I16));
+        assert_eq!(I8.widen_to_register(24), Some(I16));
+        assert_eq!(I16.widen_to_register(24), Some(I16));
+        assert_eq!(I8.widen_to_register(8), Some(I8));
+        assert_eq!(I8.widen_to_register(48), Some(I32));
+        assert_eq!(I32.widen_to_register(48), Some(I32));
+        assert_eq!(I64.widen_to_register(48), None);
+        assert_eq!(I32.widen_to_register(24), None);
+        assert_eq!(I128.widen_to_register(64), None);
+        assert_eq!(I64.widen_to_register(32), None);
+        assert_eq!(I32X4.widen_to_register(128), None);
+        assert_eq!(INVALID.widen_to_register(64), None);
     }
 
     #[test]
-    fn vectors() {
-        let big = F64.by(256).unwrap();
-        assert_eq!(big.lane_bits(), 64);
-        assert_eq!(big.lane_count(), 256);
-        assert_eq!(big.bits(), 64 * 256);
+    fn zero_value() {
+        assert!(!INVALID.has_zero());
+        assert!(!Type(0x7d).has_zero());
+        assert_eq!(INVALID.zero_bits(), 0);
+        for ty in Type::all_lane_types() {
+            assert!(ty.has_zero());
+            assert_eq!(ty.zero_bits(), ty.bits());
+        }
+        assert!(I32X4.has_zero());
+        assert_eq!(I32X4.zero_bits(), 128);
+        assert!(I32X4XN.has_zero());
+        assert_eq!(I32X4XN.zero_bits(), 128);
+    }
 
-        // Check that the generated constants match the computed vector types.
-        assert_eq!(I32.by(4), Some(I32X4));
-        assert_eq!(F64.by(8), Some(F64X8));
+    #[test]
+    fn is_atomic_capable() {
+        for ty in [I8, I16, I32, I64, F16, F32, F64] {
+            assert!(ty.is_atomic_capable(64));
+        }
+        assert!(!I128.is_atomic_capable(64));
+        assert!(!F128.is_atomic_capable(64));
+        assert!(I128.is_atomic_capable(128));
+        assert!(I32.is_atomic_capable(32));
+        assert!(!I64.is_atomic_capable(32));
+        assert!(!I32X2.is_atomic_capable(64));
+        assert!(!I32X4XN.is_atomic_capable(64));
+        assert!(!INVALID.is_atomic_capable(64));
+    }
+
+    #[test]
+    fn fits_scalar_register() {
+        assert!(I64.fits_scalar_register(64));
+        assert!(F64.fits_scalar_register(64));
+        assert!(I8.fits_scalar_register(32));
+        assert!(!I128.fits_scalar_register(64));
+        assert!(!I64.fits_scalar_register(32));
+        assert!(!I32X2.fits_scalar_register(64));
+        assert!(!I32X4.fits_scalar_register(128));
+        assert!(!INVALID.fits_scalar_register(64));
+    }
+
+    #[test]
+    fn fits_in_register() {
+        assert!(I64.fits_in_register(64));
+        assert!(I8.fits_in_register(64));
+        assert!(I32X4.fits_in_register(128));
+        assert!(!I128.fits_in_register(64));
+        assert!(!I32.by(8).unwrap().fits_in_register(128));
+        assert!(!INVALID.fits_in_register(64));
+        assert!(!I32X4XN.fits_in_register(2048));
+    }
+
+    #[test]
+    fn align_offset() {
+        assert_eq!(I32.align_offset(5), Some(8));
+        assert_eq!(I32.align_offset(8), Some(8));
+        assert_eq!(I8.align_offset(5), Some(5));
+        assert_eq!(I64.align_offset(0), Some(0));
+        assert_eq!(I64.align_offset(1), Some(8));
+        assert_eq!(I32X4.align_offset(20), Some(32));
+        assert_eq!(I32X8.align_offset(20), Some(32));
+        assert_eq!(INVALID.align_offset(3), Some(3));
+        assert_eq!(I8.align_offset(u32::MAX), Some(u32::MAX));
+        assert_eq!(I32.align_offset(u32::MAX - 3), Some(u32::MAX - 3));
+        assert_eq!(I32.align_offset(u32::MAX), None);
+        assert_eq!(I32X4.align_offset(u32::MAX - 14), None);
+    }
+
+    #[test]
+    fn minimum_alignment() {
+        assert_eq!(I8.minimum_alignment(), 1);
+        assert_eq!(I32.minimum_alignment(), 4);
+        assert_eq!(F64.minimum_alignment(), 8);
+        assert_eq!(I32X2.minimum_alignment(), 8);
+        assert_eq!(I32.by(4).unwrap().minimum_alignment(), 16);
+        assert_eq!(I32X8.minimum_alignment(), 16);
+        assert_eq!(F64X8.minimum_alignment(), 16);
+        assert_eq!(INVALID.minimum_alignment(), 1);
+    }
+
+    #[test]
+    fn max_lanes_within() {
+        assert_eq!(I32.max_lanes_within(128), 4);
+        assert_eq!(I32.max_lanes_within(127), 2);
+        assert_eq!(I32.max_lanes_within(16), 0);
+        assert_eq!(I32X4.max_lanes_within(512), 16);
+        assert_eq!(F64.max_lanes_within(100), 1);
+        assert_eq!(I8.max_lanes_within(1 << 16), 256);
+        assert_eq!(INVALID.max_lanes_within(128), 0);
     }
 
     #[test]
@@ -583,19 +3025,424 @@ mod tests {
         assert_eq!(I32.to_string(), "i32");
         assert_eq!(I64.to_string(), "i64");
         assert_eq!(I128.to_string(), "i128");
+        assert_eq!(F16.to_string(), "f16");
         assert_eq!(F32.to_string(), "f32");
         assert_eq!(F64.to_string(), "f64");
+        assert_eq!(F128.to_string(), "f128");
     }
 
     #[test]
     fn format_vectors() {
         assert_eq!(I8.by(64).unwrap().to_string(), "i8x64");
         assert_eq!(F64.by(2).unwrap().to_string(), "f64x2");
+        assert_eq!(I128.by(2).unwrap().to_string(), "i128x2");
+        assert_eq!(I128X2.bits(), 256);
+        assert_eq!(I128X4.lane_count(), 4);
+        assert_eq!(F16.by(8).unwrap().to_string(), "f16x8");
+        assert_eq!(F16X8.bits(), 128);
         assert_eq!(I8.by(3), None);
         assert_eq!(I8.by(512), None);
         assert_eq!(INVALID.by(4), None);
     }
 
+    #[test]
+    fn format_signed() {
+        assert_eq!(I8.to_signed().to_string(), "s8");
+        assert_eq!(I64.to_unsigned().to_string(), "u64");
+        assert_eq!(I32X4.to_signed().to_string(), "s32x4");
+        assert_eq!(I16X8XN.to_unsigned().to_string(), "u16x8xN");
+        assert_eq!(
+            SignedType::new(I32, Signedness::Agnostic).to_string(),
+            "i32"
+        );
+        assert_eq!(F32.to_signed().to_string(), "f32");
+
+        let ty = I16.to_unsigned();
+        assert_eq!(ty.ty(), I16);
+        assert_eq!(ty.signedness(), Signedness::Unsigned);
+        assert_eq!(I32X4XN.to_signed().signedness(), Signedness::Signed);
+        assert_eq!(F32.to_signed().signedness(), Signedness::Agnostic);
+        assert_eq!(F64X2.to_unsigned().signedness(), Signedness::Agnostic);
+        assert_eq!(INVALID.to_signed().signedness(), Signedness::Agnostic);
+    }
+
+    #[test]
+    fn format_table() {
+        // The straightforward formatting that `Display` is an optimized version of.
+        fn reference(ty: Type) -> String {
+            if ty.is_int() {
+                format!("i{}", ty.lane_bits())
+            } else if ty.is_float() {
+                format!("f{}", ty.lane_bits())
+            } else if ty.is_vector() {
+                format!("{}x{}", reference(ty.lane_type()), ty.lane_count())
+            } else {
+                format!("{}x{}xN", reference(ty.lane_type()), ty.min_lane_count())
+            }
+        }
+        for bits in 0..=u16::MAX {
+            let ty = Type(bits);
+            if ty.is_canonical() {
+                assert_eq!(ty.to_string(), reference(ty));
+            }
+        }
+        let names: Vec<String> = [I8, I128, F16, F128, I8X16, F64X2, I64X8]
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect();
+        assert_eq!(
+            names,
+            ["i8", "i128", "f16", "f128", "i8x16", "f64x2", "i64x8"]
+        );
+    }
+
+    #[test]
+    fn format_dynamic() {
+        assert_eq!(I32X4XN.to_string(), "i32x4xN");
+        assert_eq!(I8X16XN.to_string(), "i8x16xN");
+        assert_eq!(F64X2XN.to_string(), "f64x2xN");
+        assert_eq!(format!("{I32X4XN:#}"), "i32x4xN (0x116)");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown Type(0x4)")]
+    fn format_below_lanes() {
+        Type(0x04).to_string();
+    }
+
+    #[test]
+    fn c_name() {
+        assert_eq!(I8.c_name(), Some("int8_t"));
+        assert_eq!(I32.c_name(), Some("int32_t"));
+        assert_eq!(I64.c_name(), Some("int64_t"));
+        assert_eq!(F32.c_name(), Some("float"));
+        assert_eq!(F64.c_name(), Some("double"));
+        assert_eq!(INVALID.c_name(), Some("void"));
+        assert_eq!(I128.c_name(), None);
+        assert_eq!(F16.c_name(), None);
+        assert_eq!(I32X4.c_name(), None);
+        assert_eq!(I32X4XN.c_name(), None);
+    }
+
+    #[test]
+    fn write_name() {
+        let types = [I8, I32, F64, I32X4, F16X8, I128, I32X4XN];
+        let mut buf = String::new();
+        for ty in types {
+            ty.write_name(&mut buf).unwrap();
+            buf.push(' ');
+        }
+        let expected: String = types.iter().map(|ty| ty.to_string() + " ").collect();
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn format_debug() {
+        assert_eq!(format!("{I32:?}"), "types::I32");
+        assert_eq!(format!("{:?}", F64.by(2).unwrap()), "types::F64X2");
+        assert_eq!(format!("{I32X4XN:?}"), "types::I32X4XN");
+        assert_eq!(format!("{INVALID:?}"), "types::INVALID");
+        assert_eq!(format!("{:?}", Type(0x7d)), "Type(0x7d)");
+    }
+
+    #[test]
+    fn format_alternate() {
+        assert_eq!(format!("{I32:#}"), "i32 (0x76)");
+        assert_eq!(format!("{F64X2:#}"), "f64x2 (0x8b)");
+        assert_eq!(format!("{:#}", Type(0x7d)), "invalid(0x7d)");
+        assert_eq!(format!("{INVALID:#}"), "invalid(0x00)");
+    }
+
+    #[test]
+    fn parse_roundtrip() {
+        for ty in [I8, I16, I32, I64, I128, F16, F32, F64, F128] {
+            let s = ty.to_string();
+            assert_eq!(s.parse::<Type>().unwrap().to_string(), s);
+        }
+        for s in [
+            "i8x64", "i16x8", "i32x4", "i128x2", "f16x8", "f32x16", "f64x256",
+        ] {
+            assert_eq!(s.parse::<Type>().unwrap().to_string(), s);
+        }
+
+        assert_eq!("i32x4".parse(), Ok(I32X4));
+        assert_eq!("i33".parse::<Type>(), Err(ParseTypeError::UnknownLaneType));
+        assert_eq!("".parse::<Type>(), Err(ParseTypeError::UnknownLaneType));
+        assert_eq!(
+            "i8x3".parse::<Type>(),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            "i8x512".parse::<Type>(),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            "f32x".parse::<Type>(),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            "f32x4x2".parse::<Type>(),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+    }
+
+    #[test]
+    fn eq_str() {
+        assert_eq!(I32, "i32");
+        assert_eq!(F64.by(2).unwrap(), "f64x2");
+        assert!(I8X16 == *"i8x16");
+        assert_ne!(I32, "i64");
+        assert_ne!(I32, "i32x1x");
+        assert_ne!(INVALID, "invalid");
+    }
+
+    #[test]
+    fn repr_roundtrip() {
+        let mut valid = 0;
+        for bits in 0..=u16::MAX {
+            if let Some(ty) = Type::try_from_repr(bits) {
+                assert_eq!(ty.repr(), bits);
+                assert!(
+                    ty.is_invalid() || ty.is_lane() || ty.is_vector() || ty.is_dynamic_vector()
+                );
+                valid += 1;
+            }
+        }
+        // INVALID, and 9 lane types, each with 8 fixed and 8 dynamic vector lane counts.
+        assert_eq!(valid, 1 + 9 + 9 * 8 + 9 * 8);
+
+        assert_eq!(Type::try_from_repr(INVALID.repr()), Some(INVALID));
+        assert_eq!(Type::try_from_repr(I32.repr()), Some(I32));
+        assert_eq!(Type::try_from_repr(F64X2.repr()), Some(F64X2));
+        assert_eq!(Type::try_from_repr(I8X64XN.repr()), Some(I8X64XN));
+        assert_eq!(Type::try_from_repr(F128.repr() + 1), None);
+        assert_eq!(Type::try_from_repr(I8.repr() - 1), None);
+    }
+
+    #[test]
+    fn is_canonical() {
+        for ty in Type::all_vector_types(2048) {
+            assert!(ty.is_canonical());
+            if let Some(dynamic) = ty.is_vector().then(|| ty.vector_to_dynamic()).flatten() {
+                assert!(dynamic.is_canonical());
+            }
+        }
+        for ty in [I8, I128, F16, F128, I8X16, F64X2, I32X16, I32X4XN, I8X64XN] {
+            assert!(ty.is_canonical());
+        }
+        assert!(I32.by(4).unwrap().is_canonical());
+        assert!(F64.by(256).unwrap().is_canonical());
+        assert!(!INVALID.is_canonical());
+        assert!(!Type(0x04).is_canonical());
+        assert!(!Type(0x7d).is_canonical());
+        assert!(!Type(0x8f).is_canonical());
+        assert!(!Type(0x180).is_canonical());
+    }
+
+    #[test]
+    fn repr_boundaries() {
+        let widest = F128.by(256).unwrap();
+        assert_eq!(widest.repr(), 0xfc);
+        assert_eq!(Type::try_from_repr(0xfc), Some(widest));
+        assert_eq!(Type::try_from_repr(0xfd), None);
+        assert_eq!(Type::try_from_repr(0xff), None);
+        assert_eq!(Type::try_from_repr(0x17c), Some(Type(0x17c)));
+        assert_eq!(Type::try_from_repr(0x17d), None);
+        assert_eq!(Type::try_from_repr(0x180), None);
+        assert_eq!(Type::try_from_repr(0x184), None);
+    }
+
+    #[test]
+    fn encoding_fields() {
+        for ty in Type::all_vector_types(2048) {
+            assert_eq!(ty.scalar_code(), ty.lane_type().scalar_code());
+            assert_eq!(u32::from(ty.lane_shift()) - 7, ty.log2_lane_count());
+            if let Some(dynamic) = ty.is_vector().then(|| ty.vector_to_dynamic()).flatten() {
+                assert_eq!(dynamic.scalar_code(), ty.scalar_code());
+                assert_eq!(
+                    u32::from(dynamic.lane_shift()) - 0xf,
+                    dynamic.log2_min_lane_count()
+                );
+            }
+        }
+        assert_eq!(I32.scalar_code(), 0x6);
+        assert_eq!(I32.lane_shift(), 0x7);
+        assert_eq!(I32X4.scalar_code(), 0x6);
+        assert_eq!(I32X4.lane_shift(), 0x9);
+        assert_eq!(I32X4XN.lane_shift(), 0x11);
+    }
+
+    #[test]
+    fn typ_macro() {
+        assert_eq!(crate::typ!(i8), I8);
+        assert_eq!(crate::typ!(f64), F64);
+        assert_eq!(crate::typ!(i128), I128);
+        assert_eq!(crate::typ!(i32x4), I32.by(4).unwrap());
+        assert_eq!(crate::typ!(f16x8), F16.by(8).unwrap());
+        assert_eq!(crate::typ!(i8x256), I8.by(256).unwrap());
+        const TY: Type = crate::typ!(f32x4);
+        assert_eq!(TY, F32X4);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Type::from_name("i32x4"), Ok(I32X4));
+        for name in ["i64x02", "i64x0", "i64x1", "i64x00"] {
+            assert_eq!(Type::from_name(name), Err(ParseTypeError::InvalidLaneCount));
+        }
+        assert_eq!(
+            Type::from_name("i32x"),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            Type::from_name("i32x3"),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            Type::from_name("i32x+4"),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            Type::from_name("i8x512"),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            Type::from_name("i8x99999999999"),
+            Err(ParseTypeError::InvalidLaneCount)
+        );
+        assert_eq!(
+            Type::from_name("b1x8"),
+            Err(ParseTypeError::UnknownLaneType)
+        );
+        assert_eq!(Type::from_name(""), Err(ParseTypeError::UnknownLaneType));
+    }
+
+    #[test]
+    fn repr_conversions() {
+        assert_eq!(Type::try_from(u16::from(I32X4)), Ok(I32X4));
+        assert_eq!(u16::from(F64), F64.repr());
+        assert_eq!(Type::try_from(u16::from(INVALID)), Ok(INVALID));
+        assert_eq!(Type::try_from(0), Ok(INVALID));
+        assert_eq!(Type::try_from(0x7d), Err(TypeError::InvalidRepr(0x7d)));
+    }
+
+    #[test]
+    fn wire_roundtrip() {
+        for ty in Type::all_lane_types().chain([INVALID, I8X16, F64X2, I128X4, I8X64XN, F32X4XN]) {
+            assert_eq!(Type::try_from(WireType::from(ty)), Ok(ty));
+        }
+
+        assert_eq!(WireType::from(I32), WireType::I32);
+        assert_eq!(
+            WireType::from(I32X4),
+            WireType::Vector {
+                lane: Box::new(WireType::I32),
+                log2_count: 2
+            }
+        );
+        assert_eq!(WireType::from(Type(0x7d)), WireType::Invalid);
+
+        let nested = WireType::Vector {
+            lane: Box::new(WireType::from(I32X4)),
+            log2_count: 1,
+        };
+        assert_eq!(Type::try_from(nested), Err(TypeError::InvalidLane));
+        let huge = WireType::Vector {
+            lane: Box::new(WireType::I8),
+            log2_count: 200,
+        };
+        assert_eq!(Type::try_from(huge), Err(TypeError::TooManyLanes));
+        let scalar = WireType::DynamicVector {
+            lane: Box::new(WireType::I8),
+            log2_min_count: 0,
+        };
+        assert_eq!(Type::try_from(scalar), Err(TypeError::DynamicVector));
+    }
+
+    #[test]
+    fn wire_bytes() {
+        for ty in Type::all_lane_types().chain([INVALID, I8X16, F64X2, I128X4, I8X64XN, F32X4XN]) {
+            let bytes = WireType::from(ty).to_bytes();
+            assert_eq!(
+                WireType::from_bytes(&bytes),
+                Ok((WireType::from(ty), bytes.len()))
+            );
+        }
+
+        assert_eq!(WireType::from(INVALID).to_bytes(), [0]);
+        assert_eq!(WireType::from(I32).to_bytes(), [3]);
+        assert_eq!(WireType::from(F128).to_bytes(), [9]);
+        assert_eq!(WireType::from(I32X4).to_bytes(), [16, 2, 3]);
+        assert_eq!(WireType::from(F32X4XN).to_bytes(), [17, 2, 7]);
+
+        assert_eq!(WireType::from_bytes(&[3, 0xff]), Ok((WireType::I32, 1)));
+        assert_eq!(WireType::from_bytes(&[]), Err(TypeError::InvalidEncoding));
+        assert_eq!(WireType::from_bytes(&[10]), Err(TypeError::InvalidEncoding));
+        assert_eq!(
+            WireType::from_bytes(&[16, 2]),
+            Err(TypeError::InvalidEncoding)
+        );
+        assert_eq!(WireType::from_bytes(&[16]), Err(TypeError::InvalidEncoding));
+        assert_eq!(
+            WireType::from_bytes(&[16, 1, 16, 2, 3]),
+            Err(TypeError::InvalidLane)
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let mut types = vec![
+            F32X4, I128, F64, I8X16, INVALID, I32X4XN, F16, I32, I8, F32, I32X4, I16, F128, I64,
+            I32X2,
+        ];
+        types.sort();
+        assert_eq!(
+            types,
+            [
+                INVALID, I8, I8X16, I16, I32, I32X2, I32X4, I64, I128, F16, F32, F32X4, F64, F128,
+                I32X4XN,
+            ]
+        );
+    }
+
+    #[test]
+    fn canonical_order() {
+        let mut types = vec![
+            F64X2, I16X8XN, I64X2, F32, I8X8, INVALID, I16X8, I64, F32X4XN,
+        ];
+        types.sort_by(|a, b| a.cmp_canonical(*b));
+        assert_eq!(
+            types,
+            [INVALID, I8X8, I16X8, I64, I64X2, F32, F64X2, I16X8XN, F32X4XN]
+        );
+        assert_eq!(I32.cmp_canonical(I32), Ordering::Equal);
+        assert_eq!(I32X4.cmp_canonical(I32X4XN), Ordering::Less);
+        assert_eq!(F32.cmp_canonical(I64), Ordering::Greater);
+    }
+
+    #[test]
+    fn cmp_by_size() {
+        let mut types = vec![I8, F64X2, I32X4XN, I64, I32, I128, F32, I8X16, F16, INVALID];
+        types.sort_by(|a, b| Type::cmp_by_size(*a, *b));
+        assert_eq!(
+            types,
+            [I8X16, I128, F64X2, I64, I32, F32, F16, I8, INVALID, I32X4XN]
+        );
+    }
+
+    #[test]
+    fn hash_set() {
+        let types = [
+            I8, I16, I32, I64, I128, F16, F32, F64, F128, I8X16, I32X4, F64X2, I32X4XN,
+        ];
+        let set: rustc_hash::FxHashSet<Type> = types.iter().copied().collect();
+        assert_eq!(set.len(), types.len());
+        for ty in types {
+            assert!(set.contains(&ty));
+        }
+        assert!(!set.contains(&I16X8));
+    }
+
     #[test]
     fn as_truthy() {
         assert_eq!(I32X4.as_truthy(), I32X4);
@@ -604,6 +3451,72 @@ mod tests {
         assert_eq!(I32.as_truthy_pedantic(), I32);
     }
 
+    #[test]
+    fn lane_byte_offset() {
+        let offsets: Vec<_> = (0..4).map(|i| I32X4.lane_byte_offset(i)).collect();
+        assert_eq!(offsets, [Some(0), Some(4), Some(8), Some(12)]);
+        assert_eq!(I32X4.lane_byte_offset(4), None);
+        assert_eq!(I8X16.lane_byte_offset(15), Some(15));
+        assert_eq!(F64X2.lane_byte_offset(1), Some(8));
+        assert_eq!(I64.lane_byte_offset(0), Some(0));
+        assert_eq!(I64.lane_byte_offset(1), None);
+        assert_eq!(I32X4XN.lane_byte_offset(0), None);
+        assert_eq!(INVALID.lane_byte_offset(0), None);
+    }
+
+    #[test]
+    fn iconst_fits() {
+        assert!(I8.iconst_fits_signed(127));
+        assert!(I8.iconst_fits_signed(-128));
+        assert!(!I8.iconst_fits_signed(128));
+        assert!(!I8.iconst_fits_signed(200));
+        assert!(!I8.iconst_fits_signed(-129));
+        assert!(I8.iconst_fits_unsigned(200));
+        assert!(I8.iconst_fits_unsigned(255));
+        assert!(!I8.iconst_fits_unsigned(256));
+        assert!(I32.iconst_fits_signed(i32::MAX.into()));
+        assert!(I32.iconst_fits_signed(i32::MIN.into()));
+        assert!(!I32.iconst_fits_signed(i64::from(i32::MAX) + 1));
+        assert!(!I32.iconst_fits_signed(i64::from(i32::MIN) - 1));
+        assert!(I32.iconst_fits_unsigned(u32::MAX.into()));
+        assert!(!I32.iconst_fits_unsigned(u64::from(u32::MAX) + 1));
+        assert!(I64.iconst_fits_signed(i64::MIN));
+        assert!(I64.iconst_fits_unsigned(u64::MAX));
+        assert!(I128.iconst_fits_unsigned(u64::MAX));
+        assert!(!F32.iconst_fits_signed(0));
+        assert!(!F32.iconst_fits_unsigned(0));
+        assert!(!I32X4.iconst_fits_signed(0));
+        assert!(!INVALID.iconst_fits_unsigned(0));
+    }
+
+    #[test]
+    fn int_ranges() {
+        assert_eq!(I8.unsigned_max(), Some(255));
+        assert_eq!(I8.signed_max(), Some(127));
+        assert_eq!(I8.signed_min(), Some(-128));
+        assert_eq!(I32.unsigned_max(), Some(u32::MAX.into()));
+        assert_eq!(I32.signed_max(), Some(i32::MAX.into()));
+        assert_eq!(I32.signed_min(), Some(i32::MIN.into()));
+        assert_eq!(I64.unsigned_max(), Some(u64::MAX.into()));
+        assert_eq!(I64.signed_max(), Some(i64::MAX.into()));
+        assert_eq!(I64.signed_min(), Some(i64::MIN.into()));
+        assert_eq!(I128.unsigned_max(), Some(u128::MAX));
+        assert_eq!(I128.signed_min(), Some(i128::MIN));
+        assert_eq!(I16X8.unsigned_max(), Some(u16::MAX.into()));
+
+        assert_eq!(F32.unsigned_max(), None);
+        assert_eq!(F64.signed_max(), None);
+        assert_eq!(INVALID.signed_min(), None);
+    }
+
+    #[test]
+    fn extreme_scalars() {
+        assert_eq!(Type::smallest_int(), I8);
+        assert_eq!(Type::largest_int(), I128);
+        assert_eq!(Type::smallest_float(), F16);
+        assert_eq!(Type::largest_float(), F128);
+    }
+
     #[test]
     fn int_from_size() {
         assert_eq!(Type::int(0), None);
@@ -616,9 +3529,36 @@ mod tests {
         assert_eq!(Type::int_with_byte_size(6), None);
         assert_eq!(Type::int_with_byte_size(16), Some(I128));
 
+        assert_eq!(Type::int(24), None);
+        assert_eq!(Type::int(128), Some(I128));
+
         // Ensure `int_with_byte_size` handles overflow properly
         let evil = 0xE001_u16;
         assert_eq!(evil.wrapping_mul(8), 8, "check the constant is correct");
         assert_eq!(Type::int_with_byte_size(evil), None);
     }
+
+    #[test]
+    fn float_from_size() {
+        assert_eq!(Type::float(0), None);
+        assert_eq!(Type::float(16), Some(F16));
+        assert_eq!(Type::float(32), Some(F32));
+        assert_eq!(Type::float(64), Some(F64));
+        assert_eq!(Type::float(128), Some(F128));
+        assert_eq!(Type::float(8), None);
+        assert_eq!(Type::float(80), None);
+    }
+
+    mod prelude {
+        use crate::ir::types::prelude::*;
+
+        #[test]
+        fn constants() {
+            let lanes: [Type; 9] = [I8, I16, I32, I64, I128, F16, F32, F64, F128];
+            assert!(Type::all_lane_types().eq(lanes));
+            let vectors: [Type; 7] = [I8X16, I16X8, I32X4, I64X2, F16X8, F32X4, F64X2];
+            assert!(vectors.iter().all(|ty| ty.bits() == 128));
+            assert!(INVALID.is_invalid());
+        }
+    }
 }
@@ -12,6 +12,7 @@ use target_lexicon::Architecture;
 /// A trait for generating random Cranelift datastructures.
 pub trait CraneliftArbitrary {
     fn _type(&mut self, simd_enabled: bool) -> Result<Type>;
+    fn fixed_type(&mut self, max_bits: u32) -> Result<Type>;
     fn callconv(&mut self, architecture: Architecture) -> Result<CallConv>;
     fn abi_param(&mut self, simd_enabled: bool) -> Result<AbiParam>;
     fn signature(
@@ -41,6 +42,18 @@ impl<'a> CraneliftArbitrary for &mut Unstructured<'a> {
         Ok(*self.choose(choices)?)
     }
 
+    fn fixed_type(&mut self, max_bits: u32) -> Result<Type> {
+        // Any lane type or fixed-size vector type of at most `max_bits` bits, which are
+        // always valid.
+        let lanes: Vec<Type> = Type::all_lane_types()
+            .filter(|ty| ty.bits() <= max_bits)
+            .collect();
+        let lane = *self.choose(&lanes)?;
+        let max_log2_lanes = lane.max_lanes_within(max_bits).ilog2();
+        let log2_lanes = self.int_in_range(0..=max_log2_lanes)?;
+        Ok(lane.by(1 << log2_lanes).unwrap())
+    }
+
     fn callconv(&mut self, architecture: Architecture) -> Result<CallConv> {
         // These are implemented and should work on all backends
         let mut allowed_callconvs = vec![
@@ -144,3 +157,31 @@ impl<'a> CraneliftArbitrary for &mut Unstructured<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_type_roundtrip() {
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut unstructured = Unstructured::new(&data);
+        let mut u = &mut unstructured;
+        for _ in 0..1000 {
+            let ty = u.fixed_type(1024).unwrap();
+            assert!(ty.bits() <= 1024);
+            assert_eq!(ty.to_string().parse::<Type>(), Ok(ty));
+        }
+    }
+
+    #[test]
+    fn fixed_type_too_narrow() {
+        let data = [0xff; 16];
+        let mut unstructured = Unstructured::new(&data);
+        let mut u = &mut unstructured;
+        for max_bits in 0..8 {
+            assert!(u.fixed_type(max_bits).is_err());
+        }
+        assert_eq!(u.fixed_type(8).unwrap(), I8);
+    }
+}
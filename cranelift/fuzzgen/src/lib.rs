@@ -66,6 +66,11 @@ where
         )?)
     }
 
+    /// Generate a lane type or fixed-size vector type of at most `max_bits` bits.
+    pub fn generate_type(&mut self, max_bits: u32) -> Result<Type> {
+        self.u.fixed_type(max_bits)
+    }
+
     pub fn generate_test_inputs(mut self, signature: &Signature) -> Result<Vec<TestCaseInput>> {
         let mut inputs = Vec::new();
 
@@ -0,0 +1,21 @@
+#![no_main]
+
+use cranelift_codegen::ir::types::Type;
+use cranelift_fuzzgen::FuzzGen;
+use libfuzzer_sys::arbitrary::Unstructured;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let mut fuzzgen = FuzzGen::new(&mut u);
+    let Ok(ty) = fuzzgen.generate_type(u32::MAX) else {
+        return;
+    };
+
+    // Every generated type must print as a name that parses back to it, and encode as bytes
+    // that decode back to it.
+    assert_eq!(ty.to_string().parse::<Type>(), Ok(ty));
+    let mut bytes = Vec::new();
+    ty.encode(&mut bytes);
+    assert_eq!(Type::decode(&bytes), Ok((ty, bytes.len())));
+});